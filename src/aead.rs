@@ -1,8 +1,10 @@
 use std::str::FromStr;
 
+use aead::{AeadCore, KeySizeUser};
+use hpke::hybrid_array::typenum::Unsigned;
 use num_enum::TryFromPrimitive;
 
-use crate::IdLookupError;
+use crate::{AlgorithmKind, Error, IdLookupError, QuantumResistance};
 
 /// An authenticated encryption with additional data encryption function, as per [RFC9180§7.3][1].
 ///
@@ -25,6 +27,11 @@ pub enum Aead {
     /// ChaCha20Poly1305 [RFC8439](https://www.rfc-editor.org/info/rfc8439)
     #[cfg(feature = "chacha")]
     ChaCha20Poly1305 = 3,
+    /// Export-only [RFC9180§5.3](https://www.rfc-editor.org/rfc/rfc9180.html#section-5.3)
+    ///
+    /// This AEAD can only be used for secret export. Sealing or opening with it will return
+    /// [`Error::ExportOnly`].
+    ExportOnly = 0xFFFF,
 }
 
 impl FromStr for Aead {
@@ -38,7 +45,148 @@ impl FromStr for Aead {
             "aesgcm256" | "aes256gcm" => Ok(Self::AesGcm256),
             #[cfg(feature = "chacha")]
             "chacha20poly1305" => Ok(Self::ChaCha20Poly1305),
-            _ => Err(IdLookupError("aead not recognized")),
+            "exportonly" => Ok(Self::ExportOnly),
+            _ => Err(IdLookupError::name(AlgorithmKind::Aead, s)),
+        }
+    }
+}
+
+/// The id of the [`Aead`] in the [IANA HPKE registry][1], which converts back with `TryFrom<u16>`.
+///
+/// [1]: https://www.iana.org/assignments/hpke/hpke.xhtml
+impl From<Aead> for u16 {
+    fn from(aead: Aead) -> u16 {
+        aead as u16
+    }
+}
+
+/// Writes the name of the [`Aead`] in the [IANA HPKE registry][1], such as `AES-128-GCM`, which
+/// [`FromStr`] parses back.
+///
+/// [1]: https://www.iana.org/assignments/hpke/hpke.xhtml
+impl std::fmt::Display for Aead {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            #[cfg(feature = "aes")]
+            Aead::AesGcm128 => "AES-128-GCM",
+            #[cfg(feature = "aes")]
+            Aead::AesGcm256 => "AES-256-GCM",
+            #[cfg(feature = "chacha")]
+            Aead::ChaCha20Poly1305 => "ChaCha20Poly1305",
+            Aead::ExportOnly => "Export-only",
+        })
+    }
+}
+
+impl Aead {
+    /// The nominal security level of this [`Aead`] in bits: 128 or 256. This is `None` for
+    /// [`Aead::ExportOnly`], which encrypts nothing.
+    #[must_use]
+    pub const fn security_level(self) -> Option<u16> {
+        match self {
+            #[cfg(feature = "aes")]
+            Aead::AesGcm128 => Some(128),
+            #[cfg(feature = "aes")]
+            Aead::AesGcm256 => Some(256),
+            #[cfg(feature = "chacha")]
+            Aead::ChaCha20Poly1305 => Some(256),
+            Aead::ExportOnly => None,
+        }
+    }
+
+    /// Whether this [`Aead`] resists attack by a quantum computer. Every [`Aead`] is
+    /// [`QuantumResistance::PostQuantum`] at its [`security_level`](Aead::security_level), which
+    /// is how the NIST post-quantum security categories are defined.
+    #[must_use]
+    pub const fn quantum_resistance(self) -> QuantumResistance {
+        QuantumResistance::PostQuantum
+    }
+
+    /// Whether this [`Aead`] is approved for use in FIPS 140-3 validated modules. AES-GCM is
+    /// approved per [SP 800-38D][1] and ChaCha20Poly1305 is not. [`Aead::ExportOnly`] uses no
+    /// cipher, so it is treated as approved. This describes the algorithm, not the validation
+    /// status of the implementation in use.
+    ///
+    /// [1]: https://doi.org/10.6028/nist.sp.800-38d
+    #[must_use]
+    pub const fn is_fips_approved(self) -> bool {
+        match self {
+            #[cfg(feature = "aes")]
+            Aead::AesGcm128 | Aead::AesGcm256 => true,
+            #[cfg(feature = "chacha")]
+            Aead::ChaCha20Poly1305 => false,
+            Aead::ExportOnly => true,
+        }
+    }
+
+    /// The length in bytes of a key for this [`Aead`], `Nk` in [RFC9180§7.3][1]. This is zero
+    /// for [`Aead::ExportOnly`].
+    ///
+    /// [1]: https://www.rfc-editor.org/rfc/rfc9180.html#section-7.3
+    #[must_use]
+    pub const fn n_k(self) -> usize {
+        self.sizes().key
+    }
+
+    /// The length in bytes of a nonce for this [`Aead`], `Nn` in [RFC9180§7.3][1]. This is zero
+    /// for [`Aead::ExportOnly`].
+    ///
+    /// [1]: https://www.rfc-editor.org/rfc/rfc9180.html#section-7.3
+    #[must_use]
+    pub const fn n_n(self) -> usize {
+        self.sizes().nonce
+    }
+
+    /// The length in bytes of an authentication tag for this [`Aead`], `Nt` in
+    /// [RFC9180§7.3][1], which is also how much longer a ciphertext is than its plaintext. This
+    /// is zero for [`Aead::ExportOnly`].
+    ///
+    /// [1]: https://www.rfc-editor.org/rfc/rfc9180.html#section-7.3
+    #[must_use]
+    pub const fn n_t(self) -> usize {
+        self.sizes().tag
+    }
+
+    const fn sizes(self) -> Sizes {
+        match self {
+            #[cfg(feature = "aes")]
+            Aead::AesGcm128 => Sizes::of::<hpke::aead::AesGcm128>(),
+            #[cfg(feature = "aes")]
+            Aead::AesGcm256 => Sizes::of::<hpke::aead::AesGcm256>(),
+            #[cfg(feature = "chacha")]
+            Aead::ChaCha20Poly1305 => Sizes::of::<hpke::aead::ChaCha20Poly1305>(),
+            // hpke gives the export-only aead a placeholder nonce size
+            Aead::ExportOnly => Sizes {
+                key: 0,
+                nonce: 0,
+                tag: 0,
+            },
+        }
+    }
+
+    /// checks that this [`Aead`] can be used to seal and open messages
+    pub(crate) fn check_encryption(self) -> Result<(), Error> {
+        if self == Aead::ExportOnly {
+            Err(Error::ExportOnly { aead: self })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// The lengths in bytes of the parameters of an [`Aead`].
+struct Sizes {
+    key: usize,
+    nonce: usize,
+    tag: usize,
+}
+
+impl Sizes {
+    const fn of<AeadT: hpke::aead::Aead>() -> Self {
+        Self {
+            key: <AeadT::AeadImpl as KeySizeUser>::KeySize::USIZE,
+            nonce: <AeadT::AeadImpl as AeadCore>::NonceSize::USIZE,
+            tag: <AeadT::AeadImpl as AeadCore>::TagSize::USIZE,
         }
     }
 }
@@ -51,4 +199,5 @@ pub const AEAD_ALL: &[Aead] = &[
     Aead::AesGcm256,
     #[cfg(feature = "chacha")]
     Aead::ChaCha20Poly1305,
+    Aead::ExportOnly,
 ];
@@ -0,0 +1,355 @@
+use std::{
+    future::poll_fn,
+    io,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::{
+    stream::{invalid_data, StreamOpener, StreamSealer, CHUNK_PREFIX_LEN, HEADER_PREFIX_LEN},
+    Config, Error, DEFAULT_CHUNK_SIZE, MAX_CHUNK_SIZE,
+};
+
+/// The async equivalent of a [`SealingWriter`](crate::SealingWriter), writing a stream that can
+/// be opened by either an [`AsyncOpeningReader`] or an [`OpeningReader`](crate::OpeningReader).
+///
+/// The final chunk is written when the writer is [shut down](AsyncWrite::poll_shutdown), so
+/// `AsyncWriteExt::shutdown` must be called once all plaintext has been written: otherwise the
+/// stream will be rejected as truncated.
+///
+/// If any call returns an error, the stream is left in an unspecified state and must be
+/// abandoned.
+pub struct AsyncSealingWriter<W: AsyncWrite + Unpin> {
+    inner: W,
+    sealer: StreamSealer,
+    chunk_size: usize,
+    /// the plaintext of the next chunk, which is allocated up front so that it is never
+    /// reallocated, and zeroized once sealed
+    buffer: Zeroizing<Vec<u8>>,
+    /// sealed output that has not yet been written to `inner`
+    pending: Vec<u8>,
+    written: usize,
+    finished: bool,
+}
+
+impl<W: AsyncWrite + Unpin> AsyncSealingWriter<W> {
+    /// Set up an [`AsyncSealingWriter`] to `recipient_public_key` with
+    /// [`DEFAULT_CHUNK_SIZE`] byte chunks. The stream header is written to `inner` along with the
+    /// first chunk.
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if:
+    ///
+    /// * the selected [`Aead`](crate::Aead) is [export-only](crate::Aead::ExportOnly)
+    /// * we are unable to deserialize the recipient public key
+    /// * there is an error in key encapsultion
+    /// * `info` is too long for the selected [`Kdf`](crate::Kdf)
+    pub fn new(
+        config: &Config,
+        recipient_public_key: &[u8],
+        info: &[u8],
+        inner: W,
+    ) -> io::Result<Self> {
+        Self::with_chunk_size(
+            config,
+            recipient_public_key,
+            info,
+            DEFAULT_CHUNK_SIZE,
+            inner,
+        )
+    }
+
+    /// Set up an [`AsyncSealingWriter`] to `recipient_public_key` with `chunk_size` byte chunks.
+    /// The stream header is written to `inner` along with the first chunk.
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if:
+    ///
+    /// * `chunk_size` is zero or larger than [`MAX_CHUNK_SIZE`]
+    /// * the selected [`Aead`](crate::Aead) is [export-only](crate::Aead::ExportOnly)
+    /// * we are unable to deserialize the recipient public key
+    /// * there is an error in key encapsultion
+    /// * `info` is too long for the selected [`Kdf`](crate::Kdf)
+    pub fn with_chunk_size(
+        config: &Config,
+        recipient_public_key: &[u8],
+        info: &[u8],
+        chunk_size: usize,
+        inner: W,
+    ) -> io::Result<Self> {
+        if !(1..=MAX_CHUNK_SIZE).contains(&chunk_size) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "chunk size must be between 1 and MAX_CHUNK_SIZE",
+            ));
+        }
+        let (header, sealer) =
+            StreamSealer::new(config, recipient_public_key, info).map_err(io::Error::other)?;
+        Ok(Self {
+            inner,
+            sealer,
+            chunk_size,
+            buffer: Zeroizing::new(Vec::with_capacity(chunk_size)),
+            pending: header,
+            written: 0,
+            finished: false,
+        })
+    }
+
+    /// Return the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    fn seal_chunk(&mut self, last: bool) -> io::Result<()> {
+        let chunk = self
+            .sealer
+            .seal_chunk(&self.buffer, last)
+            .map_err(io::Error::other)?;
+        self.buffer.zeroize();
+        self.pending.extend_from_slice(&chunk);
+        Ok(())
+    }
+
+    fn poll_pending(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while self.written < self.pending.len() {
+            let written =
+                ready!(Pin::new(&mut self.inner).poll_write(cx, &self.pending[self.written..]))?;
+            if written == 0 {
+                return Poll::Ready(Err(io::ErrorKind::WriteZero.into()));
+            }
+            self.written += written;
+        }
+        self.pending.clear();
+        self.written = 0;
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<W: AsyncWrite + Unpin> AsyncWrite for AsyncSealingWriter<W> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        if this.finished {
+            return Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::BrokenPipe,
+                "the stream has been shut down",
+            )));
+        }
+        ready!(this.poll_pending(cx))?;
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        // a full chunk is only sealed once we know it is not the final one
+        if this.buffer.len() == this.chunk_size {
+            this.seal_chunk(false)?;
+            ready!(this.poll_pending(cx))?;
+        }
+        let len = buf.len().min(this.chunk_size - this.buffer.len());
+        this.buffer.extend_from_slice(&buf[..len]);
+        Poll::Ready(Ok(len))
+    }
+
+    /// Flushes the underlying writer. Plaintext is only written out once a full chunk has been
+    /// buffered, or when the stream is shut down.
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_pending(cx))?;
+        Pin::new(&mut this.inner).poll_flush(cx)
+    }
+
+    /// Seals any buffered plaintext into the final chunk, writes it out and shuts down the
+    /// underlying writer.
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if !this.finished {
+            ready!(this.poll_pending(cx))?;
+            this.seal_chunk(true)?;
+            this.finished = true;
+        }
+        ready!(this.poll_pending(cx))?;
+        Pin::new(&mut this.inner).poll_shutdown(cx)
+    }
+}
+
+impl<W: AsyncWrite + Unpin> std::fmt::Debug for AsyncSealingWriter<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AsyncSealingWriter")
+            .field("sealer", &self.sealer)
+            .field("chunk_size", &self.chunk_size)
+            .field("finished", &self.finished)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The async equivalent of an [`OpeningReader`](crate::OpeningReader), opening a stream written
+/// by either an [`AsyncSealingWriter`] or a [`SealingWriter`](crate::SealingWriter).
+///
+/// Errors are reported in the same way as by an [`OpeningReader`](crate::OpeningReader), and
+/// plaintext returned before an error must not be trusted to be complete.
+pub struct AsyncOpeningReader<R: AsyncRead + Unpin> {
+    inner: R,
+    opener: StreamOpener,
+    /// the chunk prefix or ciphertext being read from `inner`
+    frame: Vec<u8>,
+    filled: usize,
+    /// whether the chunk whose ciphertext is being read is the final one, or `None` while its
+    /// prefix is being read
+    last: Option<bool>,
+    plaintext: Zeroizing<Vec<u8>>,
+    position: usize,
+    at_eof: bool,
+}
+
+impl<R: AsyncRead + Unpin> AsyncOpeningReader<R> {
+    /// Read the stream header from `inner` and set up an [`AsyncOpeningReader`] with
+    /// `private_key`.
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if:
+    ///
+    /// * the selected [`Aead`](crate::Aead) is [export-only](crate::Aead::ExportOnly)
+    /// * there is an error reading the header from `inner`
+    /// * we are unable to deserialize the private key or encapsulated key
+    /// * there is an error in key decapsulation
+    /// * `info` is too long for the selected [`Kdf`](crate::Kdf)
+    pub async fn new(
+        config: &Config,
+        private_key: &[u8],
+        info: &[u8],
+        mut inner: R,
+    ) -> io::Result<Self> {
+        let mut prefix = [0; HEADER_PREFIX_LEN];
+        let mut filled = 0;
+        poll_fn(|cx| poll_fill(&mut inner, cx, &mut prefix, &mut filled)).await?;
+        let mut encapped_key = vec![0; StreamOpener::encapped_key_len(prefix)];
+        let mut filled = 0;
+        poll_fn(|cx| poll_fill(&mut inner, cx, &mut encapped_key, &mut filled)).await?;
+
+        Ok(Self {
+            inner,
+            opener: StreamOpener::new(config, private_key, &encapped_key, info)
+                .map_err(invalid_data)?,
+            frame: vec![0; CHUNK_PREFIX_LEN],
+            filled: 0,
+            last: None,
+            plaintext: Zeroizing::default(),
+            position: 0,
+            at_eof: false,
+        })
+    }
+
+    /// Return the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn poll_chunk(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        loop {
+            ready!(poll_fill(
+                &mut self.inner,
+                cx,
+                &mut self.frame,
+                &mut self.filled
+            ))?;
+
+            match self.last {
+                None => {
+                    let prefix = self.frame[..]
+                        .try_into()
+                        .expect("frame holds a chunk prefix");
+                    let (last, len) = self.opener.chunk_len(prefix).map_err(invalid_data)?;
+                    self.last = Some(last);
+                    self.frame = vec![0; len];
+                    self.filled = 0;
+                }
+
+                Some(last) => {
+                    self.plaintext = self
+                        .opener
+                        .open_chunk(last, &self.frame)
+                        .map_err(invalid_data)?;
+                    self.position = 0;
+                    self.last = None;
+                    self.frame = vec![0; CHUNK_PREFIX_LEN];
+                    self.filled = 0;
+                    return Poll::Ready(Ok(()));
+                }
+            }
+        }
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for AsyncOpeningReader<R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if buf.remaining() == 0 {
+            return Poll::Ready(Ok(()));
+        }
+        loop {
+            let remaining = &this.plaintext[this.position..];
+            if !remaining.is_empty() {
+                let len = remaining.len().min(buf.remaining());
+                buf.put_slice(&remaining[..len]);
+                this.position += len;
+                return Poll::Ready(Ok(()));
+            }
+
+            if this.opener.is_finished() {
+                if !this.at_eof {
+                    let mut byte = [0];
+                    let mut probe = ReadBuf::new(&mut byte);
+                    ready!(Pin::new(&mut this.inner).poll_read(cx, &mut probe))?;
+                    if !probe.filled().is_empty() {
+                        return Poll::Ready(Err(invalid_data(Error::MalformedStream)));
+                    }
+                    this.at_eof = true;
+                }
+                return Poll::Ready(Ok(()));
+            }
+
+            ready!(this.poll_chunk(cx))?;
+        }
+    }
+}
+
+impl<R: AsyncRead + Unpin> std::fmt::Debug for AsyncOpeningReader<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AsyncOpeningReader")
+            .field("opener", &self.opener)
+            .finish_non_exhaustive()
+    }
+}
+
+/// read from `inner` until `buf` is full, tracking progress across polls in `filled`
+fn poll_fill<R: AsyncRead + Unpin>(
+    inner: &mut R,
+    cx: &mut Context<'_>,
+    buf: &mut [u8],
+    filled: &mut usize,
+) -> Poll<io::Result<()>> {
+    while *filled < buf.len() {
+        let mut read_buf = ReadBuf::new(&mut buf[*filled..]);
+        ready!(Pin::new(&mut *inner).poll_read(cx, &mut read_buf))?;
+        let read = read_buf.filled().len();
+        if read == 0 {
+            return Poll::Ready(Err(io::ErrorKind::UnexpectedEof.into()));
+        }
+        *filled += read;
+    }
+    Poll::Ready(Ok(()))
+}
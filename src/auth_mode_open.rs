@@ -0,0 +1,68 @@
+use crate::{
+    deserialize_encapped_key, deserialize_private_key, deserialize_public_key, open_error, Config,
+    Error, Mode,
+};
+
+/// Single-shot HPKE ciphertext opening in Auth mode.
+///
+/// `auth_mode_open` provides an interface to [`hpke::single_shot_open`] with
+/// [`hpke::OpModeR::Auth`] that does not require compile time selection of an algorithm. Instead,
+/// the selected algorithm is provided through the [`Config`] passed as the first argument.
+///
+/// # Errors
+///
+/// This will return a `Result::Err` variant if:
+///
+/// * the selected [`Aead`](crate::Aead) is [export-only](crate::Aead::ExportOnly)
+/// * `info` is too long for the selected [`Kdf`](crate::Kdf)
+/// * the selected [`Kem`](crate::Kem) does not support authenticated decapsulation
+/// * we are unable to deserialize the private key, sender public key or encapsulated key
+/// * there is an error in key decapsulation
+/// * there is an error in decryption
+pub fn auth_mode_open(
+    config: &Config,
+    private_key: &[u8],
+    sender_public_key: &[u8],
+    encapped_key: &[u8],
+    info: &[u8],
+    ciphertext: &[u8],
+    aad: &[u8],
+) -> Result<Vec<u8>, Error> {
+    let Config { aead, kdf, kem } = *config;
+    aead.check_encryption()?;
+    kdf.check_key_schedule_input(&[info])?;
+    kem.check_mode(Mode::Auth)?;
+    let open = match_algo!(aead, kdf, kem, open);
+    open(
+        private_key,
+        sender_public_key,
+        encapped_key,
+        info,
+        ciphertext,
+        aad,
+    )
+}
+
+fn open<AeadT, KdfT, KemT>(
+    private_key: &[u8],
+    sender_public_key: &[u8],
+    encapped_key: &[u8],
+    info: &[u8],
+    ciphertext: &[u8],
+    aad: &[u8],
+) -> Result<Vec<u8>, Error>
+where
+    AeadT: hpke::aead::Aead,
+    KdfT: hpke::kdf::Kdf,
+    KemT: hpke::kem::Kem,
+{
+    hpke::single_shot_open::<AeadT, KdfT, KemT>(
+        &hpke::OpModeR::Auth(deserialize_public_key::<KemT>(sender_public_key)?),
+        &deserialize_private_key::<KemT>(private_key)?,
+        &deserialize_encapped_key::<KemT>(encapped_key)?,
+        info,
+        ciphertext,
+        aad,
+    )
+    .map_err(open_error::<AeadT, KemT>)
+}
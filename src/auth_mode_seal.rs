@@ -0,0 +1,77 @@
+use hpke::Serializable;
+
+use crate::{
+    deserialize_private_key, deserialize_public_key, seal_error, Config, EncappedKeyAndCiphertext,
+    Error, Mode,
+};
+
+/// Single-shot HPKE ciphertext sealing in Auth mode.
+///
+/// `auth_mode_seal` provides an interface to [`hpke::single_shot_seal`] with
+/// [`hpke::OpModeS::Auth`] that does not require compile time selection of an algorithm. Instead,
+/// the selected algorithm is provided through the [`Config`] passed as the first argument.
+///
+/// # Errors
+///
+/// This will return an `Result::Err` variant if:
+///
+/// * the selected [`Aead`](crate::Aead) is [export-only](crate::Aead::ExportOnly)
+/// * `info` is too long for the selected [`Kdf`](crate::Kdf)
+/// * the selected [`Kem`](crate::Kem) does not support authenticated encapsulation
+/// * we are unable to deserialize the recipient public key or the sender keypair
+/// * there is an error in key encapsultion
+/// * there is an error in encryption
+pub fn auth_mode_seal(
+    config: &Config,
+    recipient_public_key: &[u8],
+    sender_private_key: &[u8],
+    sender_public_key: &[u8],
+    info: &[u8],
+    plaintext: &[u8],
+    aad: &[u8],
+) -> Result<EncappedKeyAndCiphertext, Error> {
+    let Config { aead, kdf, kem } = *config;
+    aead.check_encryption()?;
+    kdf.check_key_schedule_input(&[info])?;
+    kem.check_mode(Mode::Auth)?;
+    let seal = match_algo!(aead, kdf, kem, seal);
+    seal(
+        recipient_public_key,
+        sender_private_key,
+        sender_public_key,
+        info,
+        plaintext,
+        aad,
+    )
+}
+
+fn seal<AeadT, KdfT, KemT>(
+    recipient_public_key: &[u8],
+    sender_private_key: &[u8],
+    sender_public_key: &[u8],
+    info: &[u8],
+    plaintext: &[u8],
+    aad: &[u8],
+) -> Result<EncappedKeyAndCiphertext, Error>
+where
+    AeadT: hpke::aead::Aead,
+    KdfT: hpke::kdf::Kdf,
+    KemT: hpke::kem::Kem,
+{
+    let (encapped_key, ciphertext) = hpke::single_shot_seal::<AeadT, KdfT, KemT>(
+        &hpke::OpModeS::Auth((
+            deserialize_private_key::<KemT>(sender_private_key)?,
+            deserialize_public_key::<KemT>(sender_public_key)?,
+        )),
+        &deserialize_public_key::<KemT>(recipient_public_key)?,
+        info,
+        plaintext,
+        aad,
+    )
+    .map_err(seal_error::<KemT>)?;
+
+    Ok(EncappedKeyAndCiphertext {
+        encapped_key: encapped_key.to_bytes().to_vec(),
+        ciphertext,
+    })
+}
@@ -0,0 +1,91 @@
+use hpke::Serializable;
+
+use crate::{
+    deserialize_private_key, deserialize_public_key, psk_bundle, seal_error, Config,
+    EncappedKeyAndCiphertext, Error, Mode,
+};
+
+/// Single-shot HPKE ciphertext sealing in AuthPSK mode.
+///
+/// `auth_psk_mode_seal` provides an interface to [`hpke::single_shot_seal`] with
+/// [`hpke::OpModeS::AuthPsk`] that does not require compile time selection of an algorithm.
+/// Instead, the selected algorithm is provided through the [`Config`] passed as the first argument.
+///
+/// The pre-shared key must be at least [`MIN_PSK_LEN`](crate::MIN_PSK_LEN) bytes long, and the
+/// pre-shared key id must not be empty.
+///
+/// # Errors
+///
+/// This will return an `Result::Err` variant if:
+///
+/// * the selected [`Aead`](crate::Aead) is [export-only](crate::Aead::ExportOnly)
+/// * `info`, the pre-shared key or the pre-shared key id is too long for the selected
+///   [`Kdf`](crate::Kdf)
+/// * the selected [`Kem`](crate::Kem) does not support authenticated encapsulation
+/// * the pre-shared key is too short or the pre-shared key id is empty
+/// * we are unable to deserialize the recipient public key or the sender keypair
+/// * there is an error in key encapsultion
+/// * there is an error in encryption
+pub fn auth_psk_mode_seal(
+    config: &Config,
+    recipient_public_key: &[u8],
+    sender_private_key: &[u8],
+    sender_public_key: &[u8],
+    psk: &[u8],
+    psk_id: &[u8],
+    info: &[u8],
+    plaintext: &[u8],
+    aad: &[u8],
+) -> Result<EncappedKeyAndCiphertext, Error> {
+    let Config { aead, kdf, kem } = *config;
+    aead.check_encryption()?;
+    kdf.check_key_schedule_input(&[info, psk, psk_id])?;
+    kem.check_mode(Mode::AuthPsk)?;
+    let seal = match_algo!(aead, kdf, kem, seal);
+    seal(
+        recipient_public_key,
+        sender_private_key,
+        sender_public_key,
+        psk,
+        psk_id,
+        info,
+        plaintext,
+        aad,
+    )
+}
+
+fn seal<AeadT, KdfT, KemT>(
+    recipient_public_key: &[u8],
+    sender_private_key: &[u8],
+    sender_public_key: &[u8],
+    psk: &[u8],
+    psk_id: &[u8],
+    info: &[u8],
+    plaintext: &[u8],
+    aad: &[u8],
+) -> Result<EncappedKeyAndCiphertext, Error>
+where
+    AeadT: hpke::aead::Aead,
+    KdfT: hpke::kdf::Kdf,
+    KemT: hpke::kem::Kem,
+{
+    let (encapped_key, ciphertext) = hpke::single_shot_seal::<AeadT, KdfT, KemT>(
+        &hpke::OpModeS::AuthPsk(
+            (
+                deserialize_private_key::<KemT>(sender_private_key)?,
+                deserialize_public_key::<KemT>(sender_public_key)?,
+            ),
+            psk_bundle::<KdfT>(psk, psk_id)?,
+        ),
+        &deserialize_public_key::<KemT>(recipient_public_key)?,
+        info,
+        plaintext,
+        aad,
+    )
+    .map_err(seal_error::<KemT>)?;
+
+    Ok(EncappedKeyAndCiphertext {
+        encapped_key: encapped_key.to_bytes().to_vec(),
+        ciphertext,
+    })
+}
@@ -1,6 +1,6 @@
-use hpke::HpkeError;
+use zeroize::Zeroizing;
 
-use crate::{from_bytes, Config};
+use crate::{deserialize_encapped_key, deserialize_private_key, open_error, Config, Error};
 
 /// Single-shot HPKE ciphertext opening.
 ///
@@ -12,6 +12,8 @@ use crate::{from_bytes, Config};
 ///
 /// This will return a `Result::Err` variant if:
 ///
+/// * the selected [`Aead`](crate::Aead) is [export-only](crate::Aead::ExportOnly)
+/// * `info` is too long for the selected [`Kdf`](crate::Kdf)
 /// * we are unable to deserialize the private key or encapsulated key
 /// * there is an error in key decapsulation
 /// * there is an error in decryption
@@ -22,19 +24,46 @@ pub fn base_mode_open(
     info: &[u8],
     ciphertext: &[u8],
     aad: &[u8],
-) -> Result<Vec<u8>, HpkeError> {
+) -> Result<Vec<u8>, Error> {
     let Config { aead, kdf, kem } = *config;
+    aead.check_encryption()?;
+    kdf.check_key_schedule_input(&[info])?;
     let open = match_algo!(aead, kdf, kem, open);
     open(private_key, encapped_key, info, ciphertext, aad)
 }
 
+/// Single-shot HPKE ciphertext opening, for plaintexts that are themselves secrets.
+///
+/// `base_mode_open_zeroizing` is identical to [`base_mode_open`], except that the plaintext is
+/// returned in a [`Zeroizing`] buffer, which is zeroized when it is dropped.
+///
+/// # Errors
+///
+/// This will return a `Result::Err` variant if:
+///
+/// * the selected [`Aead`](crate::Aead) is [export-only](crate::Aead::ExportOnly)
+/// * `info` is too long for the selected [`Kdf`](crate::Kdf)
+/// * we are unable to deserialize the private key or encapsulated key
+/// * there is an error in key decapsulation
+/// * there is an error in decryption
+pub fn base_mode_open_zeroizing(
+    config: &Config,
+    private_key: &[u8],
+    encapped_key: &[u8],
+    info: &[u8],
+    ciphertext: &[u8],
+    aad: &[u8],
+) -> Result<Zeroizing<Vec<u8>>, Error> {
+    base_mode_open(config, private_key, encapped_key, info, ciphertext, aad).map(Zeroizing::new)
+}
+
 fn open<AeadT, KdfT, KemT>(
     private_key: &[u8],
     encapped_key: &[u8],
     info: &[u8],
     ciphertext: &[u8],
     aad: &[u8],
-) -> Result<Vec<u8>, HpkeError>
+) -> Result<Vec<u8>, Error>
 where
     AeadT: hpke::aead::Aead,
     KdfT: hpke::kdf::Kdf,
@@ -42,10 +71,11 @@ where
 {
     hpke::single_shot_open::<AeadT, KdfT, KemT>(
         &hpke::OpModeR::Base,
-        &from_bytes(private_key)?,
-        &from_bytes(encapped_key)?,
+        &deserialize_private_key::<KemT>(private_key)?,
+        &deserialize_encapped_key::<KemT>(encapped_key)?,
         info,
         ciphertext,
         aad,
     )
+    .map_err(open_error::<AeadT, KemT>)
 }
@@ -1,6 +1,6 @@
-use hpke::{HpkeError, Serializable};
+use hpke::Serializable;
 
-use crate::{from_bytes, Config, EncappedKeyAndCiphertext};
+use crate::{deserialize_public_key, seal_error, Config, EncappedKeyAndCiphertext, Error};
 
 /// Single-shot HPKE ciphertext sealing.
 ///
@@ -12,6 +12,8 @@ use crate::{from_bytes, Config, EncappedKeyAndCiphertext};
 ///
 /// This will return an `Result::Err` variant if:
 ///
+/// * the selected [`Aead`](crate::Aead) is [export-only](crate::Aead::ExportOnly)
+/// * `info` is too long for the selected [`Kdf`](crate::Kdf)
 /// * we are unable to deserialize the recipient public key
 /// * there is an error in key encapsultion
 /// * there is an error in encryption
@@ -21,8 +23,10 @@ pub fn base_mode_seal(
     info: &[u8],
     plaintext: &[u8],
     aad: &[u8],
-) -> Result<EncappedKeyAndCiphertext, HpkeError> {
+) -> Result<EncappedKeyAndCiphertext, Error> {
     let Config { aead, kdf, kem } = *config;
+    aead.check_encryption()?;
+    kdf.check_key_schedule_input(&[info])?;
     let seal = match_algo!(aead, kdf, kem, seal);
     seal(recipient_public_key, info, plaintext, aad)
 }
@@ -32,7 +36,7 @@ fn seal<AeadT, KdfT, KemT>(
     info: &[u8],
     plaintext: &[u8],
     aad: &[u8],
-) -> Result<EncappedKeyAndCiphertext, HpkeError>
+) -> Result<EncappedKeyAndCiphertext, Error>
 where
     AeadT: hpke::aead::Aead,
     KdfT: hpke::kdf::Kdf,
@@ -40,11 +44,12 @@ where
 {
     let (encapped_key, ciphertext) = hpke::single_shot_seal::<AeadT, KdfT, KemT>(
         &hpke::OpModeS::Base,
-        &from_bytes(recipient_public_key)?,
+        &deserialize_public_key::<KemT>(recipient_public_key)?,
         info,
         plaintext,
         aad,
-    )?;
+    )
+    .map_err(seal_error::<KemT>)?;
 
     Ok(EncappedKeyAndCiphertext {
         encapped_key: encapped_key.to_bytes().to_vec(),
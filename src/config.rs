@@ -1,14 +1,21 @@
-use hpke::HpkeError;
+use std::str::FromStr;
+
+use zeroize::Zeroizing;
 
 use crate::{
-    base_mode_open, base_mode_seal, Aead, EncappedKeyAndCiphertext, IdLookupError, Kdf, Kem,
+    auth_mode_open, auth_mode_seal, auth_psk_mode_open, auth_psk_mode_seal, base_mode_open,
+    base_mode_open_zeroizing, base_mode_seal, psk_mode_open, psk_mode_seal, receiver_export,
+    sender_export, setup_receiver, setup_sender, Aead, AlgorithmKind, AsEncappedKey, AsPrivateKey,
+    AsPublicKey, EncappedKeyAndCiphertext, EncappedKeyAndSecret, Error, IdLookupError, Kdf, Kem,
+    ReceiverContext, SenderContext,
 };
 
 /// Configuration for crate interfaces.
 ///
 /// Contains an ([`Aead`], [`Kdf`], [`Kem`]) algorithmic triple. This can be used with
 /// [`Config::base_mode_seal`], [`Config::base_mode_open`], [`base_mode_seal`], or
-/// [`base_mode_open`].
+/// [`base_mode_open`], as well as their PSK, Auth and AuthPSK mode counterparts, and to set up
+/// stateful contexts with [`Config::setup_sender`] and [`Config::setup_receiver`].
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(
     feature = "serde",
@@ -25,6 +32,39 @@ pub struct Config {
     pub kem: Kem,
 }
 
+/// Writes the [`Kem`], [`Kdf`] and [`Aead`] of the [`Config`] as their IANA names, separated by
+/// `/`, such as `DHKEM(X25519, HKDF-SHA256)/HKDF-SHA256/AES-128-GCM`, which [`FromStr`] parses
+/// back.
+impl std::fmt::Display for Config {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Self { aead, kdf, kem } = self;
+        write!(f, "{kem}/{kdf}/{aead}")
+    }
+}
+
+/// Parses a [`Config`] from the names of its [`Kem`], [`Kdf`] and [`Aead`], in that order,
+/// separated by `/` or `:`, with each name in any form accepted by its own [`FromStr`]. Both
+/// `DHKEM(X25519, HKDF-SHA256)/HKDF-SHA256/AES-128-GCM` and `x25519-sha256:sha256:aes128gcm`
+/// are accepted.
+impl FromStr for Config {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let separator = if s.contains('/') { '/' } else { ':' };
+        let mut names = s.split(separator).map(str::trim);
+        let (Some(kem), Some(kdf), Some(aead), None) =
+            (names.next(), names.next(), names.next(), names.next())
+        else {
+            return Err(Error::InvalidConfigString);
+        };
+        Ok(Self {
+            aead: aead.parse()?,
+            kdf: kdf.parse()?,
+            kem: kem.parse()?,
+        })
+    }
+}
+
 impl Config {
     /// Single-shot HPKE ciphertext sealing.
     ///
@@ -36,17 +76,25 @@ impl Config {
     ///
     /// This will return a `Result::Err` variant if:
     ///
+    /// * the selected [`Aead`] is [export-only](Aead::ExportOnly)
+    /// * `info` is too long for the selected [`Kdf`]
     /// * we are unable to deserialize the recipient public key
     /// * there is an error in key encapsultion
     /// * there is an error in encryption
     pub fn base_mode_seal(
         &self,
-        recipient_public_key: &[u8],
+        recipient_public_key: &(impl AsPublicKey + ?Sized),
         info: &[u8],
         plaintext: &[u8],
         aad: &[u8],
-    ) -> Result<EncappedKeyAndCiphertext, HpkeError> {
-        base_mode_seal(self, recipient_public_key, info, plaintext, aad)
+    ) -> Result<EncappedKeyAndCiphertext, Error> {
+        base_mode_seal(
+            self,
+            recipient_public_key.public_key_bytes(self.kem)?,
+            info,
+            plaintext,
+            aad,
+        )
     }
 
     /// Single-shot HPKE ciphertext opening.
@@ -59,18 +107,481 @@ impl Config {
     ///
     /// This will return a `Result::Err` variant if:
     ///
+    /// * the selected [`Aead`] is [export-only](Aead::ExportOnly)
+    /// * `info` is too long for the selected [`Kdf`]
     /// * we are unable to deserialize the private key or encapsulated key
     /// * there is an error in key decapsulation
     /// * there is an error in decryption
     pub fn base_mode_open(
         &self,
-        private_key: &[u8],
-        encapped_key: &[u8],
+        private_key: &(impl AsPrivateKey + ?Sized),
+        encapped_key: &(impl AsEncappedKey + ?Sized),
+        info: &[u8],
+        ciphertext: &[u8],
+        aad: &[u8],
+    ) -> Result<Vec<u8>, Error> {
+        base_mode_open(
+            self,
+            private_key.private_key_bytes(self.kem)?,
+            encapped_key.encapped_key_bytes(self.kem)?,
+            info,
+            ciphertext,
+            aad,
+        )
+    }
+
+    /// Single-shot HPKE ciphertext opening, for plaintexts that are themselves secrets.
+    ///
+    /// `base_mode_open_zeroizing` is identical to [`Config::base_mode_open`], except that the
+    /// plaintext is returned in a [`Zeroizing`] buffer, which is zeroized when it is dropped.
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if:
+    ///
+    /// * the selected [`Aead`] is [export-only](Aead::ExportOnly)
+    /// * `info` is too long for the selected [`Kdf`]
+    /// * we are unable to deserialize the private key or encapsulated key
+    /// * there is an error in key decapsulation
+    /// * there is an error in decryption
+    pub fn base_mode_open_zeroizing(
+        &self,
+        private_key: &(impl AsPrivateKey + ?Sized),
+        encapped_key: &(impl AsEncappedKey + ?Sized),
+        info: &[u8],
+        ciphertext: &[u8],
+        aad: &[u8],
+    ) -> Result<Zeroizing<Vec<u8>>, Error> {
+        base_mode_open_zeroizing(
+            self,
+            private_key.private_key_bytes(self.kem)?,
+            encapped_key.encapped_key_bytes(self.kem)?,
+            info,
+            ciphertext,
+            aad,
+        )
+    }
+
+    /// Single-shot HPKE ciphertext sealing in PSK mode.
+    ///
+    /// `psk_mode_seal` provides an interface to [`hpke::single_shot_seal`] with
+    /// [`hpke::OpModeS::Psk`] that does not require compile time selection of an algorithm.
+    /// Instead, the selected algorithm is provided through the receiver.
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if:
+    ///
+    /// * the selected [`Aead`] is [export-only](Aead::ExportOnly)
+    /// * `info`, the pre-shared key or the pre-shared key id is too long for the selected [`Kdf`]
+    /// * the pre-shared key is shorter than [`MIN_PSK_LEN`](crate::MIN_PSK_LEN) or the pre-shared
+    ///   key id is empty
+    /// * we are unable to deserialize the recipient public key
+    /// * there is an error in key encapsultion
+    /// * there is an error in encryption
+    pub fn psk_mode_seal(
+        &self,
+        recipient_public_key: &(impl AsPublicKey + ?Sized),
+        psk: &[u8],
+        psk_id: &[u8],
+        info: &[u8],
+        plaintext: &[u8],
+        aad: &[u8],
+    ) -> Result<EncappedKeyAndCiphertext, Error> {
+        psk_mode_seal(
+            self,
+            recipient_public_key.public_key_bytes(self.kem)?,
+            psk,
+            psk_id,
+            info,
+            plaintext,
+            aad,
+        )
+    }
+
+    /// Single-shot HPKE ciphertext opening in PSK mode.
+    ///
+    /// `psk_mode_open` provides an interface to [`hpke::single_shot_open`] with
+    /// [`hpke::OpModeR::Psk`] that does not require compile time selection of an algorithm.
+    /// Instead, the selected algorithm is provided through the receiver.
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if:
+    ///
+    /// * the selected [`Aead`] is [export-only](Aead::ExportOnly)
+    /// * `info`, the pre-shared key or the pre-shared key id is too long for the selected [`Kdf`]
+    /// * the pre-shared key is shorter than [`MIN_PSK_LEN`](crate::MIN_PSK_LEN) or the pre-shared
+    ///   key id is empty
+    /// * we are unable to deserialize the private key or encapsulated key
+    /// * there is an error in key decapsulation
+    /// * there is an error in decryption
+    pub fn psk_mode_open(
+        &self,
+        private_key: &(impl AsPrivateKey + ?Sized),
+        encapped_key: &(impl AsEncappedKey + ?Sized),
+        psk: &[u8],
+        psk_id: &[u8],
+        info: &[u8],
+        ciphertext: &[u8],
+        aad: &[u8],
+    ) -> Result<Vec<u8>, Error> {
+        psk_mode_open(
+            self,
+            private_key.private_key_bytes(self.kem)?,
+            encapped_key.encapped_key_bytes(self.kem)?,
+            psk,
+            psk_id,
+            info,
+            ciphertext,
+            aad,
+        )
+    }
+
+    /// Single-shot HPKE ciphertext sealing in Auth mode.
+    ///
+    /// `auth_mode_seal` provides an interface to [`hpke::single_shot_seal`] with
+    /// [`hpke::OpModeS::Auth`] that does not require compile time selection of an algorithm.
+    /// Instead, the selected algorithm is provided through the receiver.
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if:
+    ///
+    /// * the selected [`Aead`] is [export-only](Aead::ExportOnly)
+    /// * `info` is too long for the selected [`Kdf`]
+    /// * the selected [`Kem`] does not [support authentication](Kem::supports_auth)
+    /// * we are unable to deserialize the recipient public key or the sender keypair
+    /// * there is an error in key encapsultion
+    /// * there is an error in encryption
+    pub fn auth_mode_seal(
+        &self,
+        recipient_public_key: &(impl AsPublicKey + ?Sized),
+        sender_private_key: &(impl AsPrivateKey + ?Sized),
+        sender_public_key: &(impl AsPublicKey + ?Sized),
+        info: &[u8],
+        plaintext: &[u8],
+        aad: &[u8],
+    ) -> Result<EncappedKeyAndCiphertext, Error> {
+        auth_mode_seal(
+            self,
+            recipient_public_key.public_key_bytes(self.kem)?,
+            sender_private_key.private_key_bytes(self.kem)?,
+            sender_public_key.public_key_bytes(self.kem)?,
+            info,
+            plaintext,
+            aad,
+        )
+    }
+
+    /// Single-shot HPKE ciphertext opening in Auth mode.
+    ///
+    /// `auth_mode_open` provides an interface to [`hpke::single_shot_open`] with
+    /// [`hpke::OpModeR::Auth`] that does not require compile time selection of an algorithm.
+    /// Instead, the selected algorithm is provided through the receiver.
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if:
+    ///
+    /// * the selected [`Aead`] is [export-only](Aead::ExportOnly)
+    /// * `info` is too long for the selected [`Kdf`]
+    /// * the selected [`Kem`] does not [support authentication](Kem::supports_auth)
+    /// * we are unable to deserialize the private key, sender public key or encapsulated key
+    /// * there is an error in key decapsulation
+    /// * there is an error in decryption
+    pub fn auth_mode_open(
+        &self,
+        private_key: &(impl AsPrivateKey + ?Sized),
+        sender_public_key: &(impl AsPublicKey + ?Sized),
+        encapped_key: &(impl AsEncappedKey + ?Sized),
         info: &[u8],
         ciphertext: &[u8],
         aad: &[u8],
-    ) -> Result<Vec<u8>, HpkeError> {
-        base_mode_open(self, private_key, encapped_key, info, ciphertext, aad)
+    ) -> Result<Vec<u8>, Error> {
+        auth_mode_open(
+            self,
+            private_key.private_key_bytes(self.kem)?,
+            sender_public_key.public_key_bytes(self.kem)?,
+            encapped_key.encapped_key_bytes(self.kem)?,
+            info,
+            ciphertext,
+            aad,
+        )
+    }
+
+    /// Single-shot HPKE ciphertext sealing in AuthPSK mode.
+    ///
+    /// `auth_psk_mode_seal` provides an interface to [`hpke::single_shot_seal`] with
+    /// [`hpke::OpModeS::AuthPsk`] that does not require compile time selection of an algorithm.
+    /// Instead, the selected algorithm is provided through the receiver.
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if:
+    ///
+    /// * the selected [`Aead`] is [export-only](Aead::ExportOnly)
+    /// * `info`, the pre-shared key or the pre-shared key id is too long for the selected [`Kdf`]
+    /// * the selected [`Kem`] does not [support authentication](Kem::supports_auth)
+    /// * the pre-shared key is shorter than [`MIN_PSK_LEN`](crate::MIN_PSK_LEN) or the pre-shared
+    ///   key id is empty
+    /// * we are unable to deserialize the recipient public key or the sender keypair
+    /// * there is an error in key encapsultion
+    /// * there is an error in encryption
+    pub fn auth_psk_mode_seal(
+        &self,
+        recipient_public_key: &(impl AsPublicKey + ?Sized),
+        sender_private_key: &(impl AsPrivateKey + ?Sized),
+        sender_public_key: &(impl AsPublicKey + ?Sized),
+        psk: &[u8],
+        psk_id: &[u8],
+        info: &[u8],
+        plaintext: &[u8],
+        aad: &[u8],
+    ) -> Result<EncappedKeyAndCiphertext, Error> {
+        auth_psk_mode_seal(
+            self,
+            recipient_public_key.public_key_bytes(self.kem)?,
+            sender_private_key.private_key_bytes(self.kem)?,
+            sender_public_key.public_key_bytes(self.kem)?,
+            psk,
+            psk_id,
+            info,
+            plaintext,
+            aad,
+        )
+    }
+
+    /// Single-shot HPKE ciphertext opening in AuthPSK mode.
+    ///
+    /// `auth_psk_mode_open` provides an interface to [`hpke::single_shot_open`] with
+    /// [`hpke::OpModeR::AuthPsk`] that does not require compile time selection of an algorithm.
+    /// Instead, the selected algorithm is provided through the receiver.
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if:
+    ///
+    /// * the selected [`Aead`] is [export-only](Aead::ExportOnly)
+    /// * `info`, the pre-shared key or the pre-shared key id is too long for the selected [`Kdf`]
+    /// * the selected [`Kem`] does not [support authentication](Kem::supports_auth)
+    /// * the pre-shared key is shorter than [`MIN_PSK_LEN`](crate::MIN_PSK_LEN) or the pre-shared
+    ///   key id is empty
+    /// * we are unable to deserialize the private key, sender public key or encapsulated key
+    /// * there is an error in key decapsulation
+    /// * there is an error in decryption
+    pub fn auth_psk_mode_open(
+        &self,
+        private_key: &(impl AsPrivateKey + ?Sized),
+        sender_public_key: &(impl AsPublicKey + ?Sized),
+        encapped_key: &(impl AsEncappedKey + ?Sized),
+        psk: &[u8],
+        psk_id: &[u8],
+        info: &[u8],
+        ciphertext: &[u8],
+        aad: &[u8],
+    ) -> Result<Vec<u8>, Error> {
+        auth_psk_mode_open(
+            self,
+            private_key.private_key_bytes(self.kem)?,
+            sender_public_key.public_key_bytes(self.kem)?,
+            encapped_key.encapped_key_bytes(self.kem)?,
+            psk,
+            psk_id,
+            info,
+            ciphertext,
+            aad,
+        )
+    }
+
+    /// Set up a stateful Base mode HPKE encryption context.
+    ///
+    /// `setup_sender` provides an interface to [`hpke::setup_sender`] that does not require
+    /// compile time selection of an algorithm. Instead, the selected algorithm is provided through
+    /// the receiver.
+    ///
+    /// Returns the encapsulated key, which must be conveyed to the recipient, and the
+    /// [`SenderContext`].
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if:
+    ///
+    /// * we are unable to deserialize the recipient public key
+    /// * there is an error in key encapsultion
+    /// * `info` is too long for the selected [`Kdf`]
+    pub fn setup_sender(
+        &self,
+        recipient_public_key: &(impl AsPublicKey + ?Sized),
+        info: &[u8],
+    ) -> Result<(Vec<u8>, SenderContext), Error> {
+        setup_sender(self, recipient_public_key.public_key_bytes(self.kem)?, info)
+    }
+
+    /// Set up a stateful Base mode HPKE decryption context.
+    ///
+    /// `setup_receiver` provides an interface to [`hpke::setup_receiver`] that does not require
+    /// compile time selection of an algorithm. Instead, the selected algorithm is provided through
+    /// the receiver.
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if:
+    ///
+    /// * we are unable to deserialize the private key or encapsulated key
+    /// * there is an error in key decapsulation
+    /// * `info` is too long for the selected [`Kdf`]
+    pub fn setup_receiver(
+        &self,
+        private_key: &(impl AsPrivateKey + ?Sized),
+        encapped_key: &(impl AsEncappedKey + ?Sized),
+        info: &[u8],
+    ) -> Result<ReceiverContext, Error> {
+        setup_receiver(
+            self,
+            private_key.private_key_bytes(self.kem)?,
+            encapped_key.encapped_key_bytes(self.kem)?,
+            info,
+        )
+    }
+
+    /// Resume a [`SenderContext`] from a blob produced by [`SenderContext::save_state`], checking
+    /// that it was saved for this [`Config`].
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if:
+    ///
+    /// * the blob is [not a valid sender context state](Error::InvalidContextState)
+    /// * the blob was saved for a [different `Config`](Error::ContextConfigMismatch)
+    pub fn resume_sender(&self, state: &[u8]) -> Result<SenderContext, Error> {
+        SenderContext::resume(self, state)
+    }
+
+    /// Resume a [`ReceiverContext`] from a blob produced by [`ReceiverContext::save_state`],
+    /// checking that it was saved for this [`Config`].
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if:
+    ///
+    /// * the blob is [not a valid receiver context state](Error::InvalidContextState)
+    /// * the blob was saved for a [different `Config`](Error::ContextConfigMismatch)
+    pub fn resume_receiver(&self, state: &[u8]) -> Result<ReceiverContext, Error> {
+        ReceiverContext::resume(self, state)
+    }
+
+    /// Single-shot HPKE secret export for the sender.
+    ///
+    /// `sender_export` implements `SendExport` from [RFC9180§6.2][1] without requiring compile
+    /// time selection of an algorithm. Instead, the selected algorithm is provided through the
+    /// receiver.
+    ///
+    /// [1]: https://www.rfc-editor.org/rfc/rfc9180.html#section-6.2
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if:
+    ///
+    /// * we are unable to deserialize the recipient public key
+    /// * there is an error in key encapsultion
+    /// * `info` is too long for the selected [`Kdf`]
+    /// * `len` is too large for the selected [`Kdf`]
+    pub fn sender_export(
+        &self,
+        recipient_public_key: &(impl AsPublicKey + ?Sized),
+        info: &[u8],
+        exporter_context: &[u8],
+        len: usize,
+    ) -> Result<EncappedKeyAndSecret, Error> {
+        sender_export(
+            self,
+            recipient_public_key.public_key_bytes(self.kem)?,
+            info,
+            exporter_context,
+            len,
+        )
+    }
+
+    /// Single-shot HPKE secret export for the recipient.
+    ///
+    /// `receiver_export` implements `ReceiveExport` from [RFC9180§6.2][1] without requiring
+    /// compile time selection of an algorithm. Instead, the selected algorithm is provided through
+    /// the receiver.
+    ///
+    /// [1]: https://www.rfc-editor.org/rfc/rfc9180.html#section-6.2
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if:
+    ///
+    /// * we are unable to deserialize the private key or encapsulated key
+    /// * there is an error in key decapsulation
+    /// * `info` is too long for the selected [`Kdf`]
+    /// * `len` is too large for the selected [`Kdf`]
+    pub fn receiver_export(
+        &self,
+        private_key: &(impl AsPrivateKey + ?Sized),
+        encapped_key: &(impl AsEncappedKey + ?Sized),
+        info: &[u8],
+        exporter_context: &[u8],
+        len: usize,
+    ) -> Result<Zeroizing<Vec<u8>>, Error> {
+        receiver_export(
+            self,
+            private_key.private_key_bytes(self.kem)?,
+            encapped_key.encapped_key_bytes(self.kem)?,
+            info,
+            exporter_context,
+            len,
+        )
+    }
+
+    /// The length in bytes of the ciphertext that sealing a plaintext of `plaintext_len` bytes
+    /// with this [`Config`] produces, not including the encapsulated key, which is
+    /// [`Kem::n_enc`] bytes long. Returns `None` for [`Aead::ExportOnly`], which cannot seal, or if
+    /// the length would overflow a `usize`.
+    #[must_use]
+    pub fn ciphertext_len(&self, plaintext_len: usize) -> Option<usize> {
+        if self.aead == Aead::ExportOnly {
+            return None;
+        }
+        plaintext_len.checked_add(self.aead.n_t())
+    }
+
+    /// The `suite_id` of this [`Config`], `"HPKE" || I2OSP(kem_id, 2) || I2OSP(kdf_id, 2) ||
+    /// I2OSP(aead_id, 2)` as defined in [RFC9180§5.1][1], which [`Config::from_suite_id`] parses
+    /// back.
+    ///
+    /// [1]: https://www.rfc-editor.org/rfc/rfc9180.html#section-5.1
+    #[must_use]
+    pub fn suite_id(&self) -> [u8; 10] {
+        let mut suite_id = [0; 10];
+        suite_id[..4].copy_from_slice(b"HPKE");
+        suite_id[4..6].copy_from_slice(&u16::from(self.kem).to_be_bytes());
+        suite_id[6..8].copy_from_slice(&u16::from(self.kdf).to_be_bytes());
+        suite_id[8..].copy_from_slice(&u16::from(self.aead).to_be_bytes());
+        suite_id
+    }
+
+    /// Parse a [`Config`] from its RFC9180 `suite_id`, as produced by [`Config::suite_id`].
+    ///
+    /// # Errors
+    ///
+    /// This will return [`Error::InvalidSuiteId`] if `suite_id` is not ten bytes starting with
+    /// `"HPKE"`, or [`Error::UnsupportedAlgorithm`] if any of its ids does not identify an
+    /// algorithm supported by this build of the crate.
+    pub fn from_suite_id(suite_id: &[u8]) -> Result<Config, Error> {
+        let Ok([b'H', b'P', b'K', b'E', kem_0, kem_1, kdf_0, kdf_1, aead_0, aead_1]) =
+            <[u8; 10]>::try_from(suite_id)
+        else {
+            return Err(Error::InvalidSuiteId);
+        };
+        Config::try_from_ids(
+            u16::from_be_bytes([aead_0, aead_1]),
+            u16::from_be_bytes([kdf_0, kdf_1]),
+            u16::from_be_bytes([kem_0, kem_1]),
+        )
     }
 
     /// Attempt to convert three u16 ids into a valid [`Config`].
@@ -78,11 +589,47 @@ impl Config {
     /// The ID mappings are defined in the [IANA HPKE registries][1].
     ///
     /// [1]: https://www.iana.org/assignments/hpke/hpke.xhtml
-    pub fn try_from_ids(aead_id: u16, kdf_id: u16, kem_id: u16) -> Result<Config, IdLookupError> {
+    ///
+    /// # Errors
+    ///
+    /// This will return [`Error::UnsupportedAlgorithm`], with the [`IdLookupError`] of the first of
+    /// the ids that does not identify an algorithm supported by this build of the crate.
+    pub fn try_from_ids(aead_id: u16, kdf_id: u16, kem_id: u16) -> Result<Config, Error> {
         Ok(Self {
-            aead: aead_id.try_into().map_err(|_| IdLookupError("aead"))?,
-            kdf: kdf_id.try_into().map_err(|_| IdLookupError("kdf"))?,
-            kem: kem_id.try_into().map_err(|_| IdLookupError("kem"))?,
+            aead: aead_id
+                .try_into()
+                .map_err(|_| IdLookupError::id(AlgorithmKind::Aead, aead_id))?,
+            kdf: kdf_id
+                .try_into()
+                .map_err(|_| IdLookupError::id(AlgorithmKind::Kdf, kdf_id))?,
+            kem: kem_id
+                .try_into()
+                .map_err(|_| IdLookupError::id(AlgorithmKind::Kem, kem_id))?,
         })
     }
+
+    /// Choose the [`Config`] to use with a peer: the first of our `preferences`, in order, that
+    /// the peer also `advertised`. Returns `None` if there is no [`Config`] in common.
+    #[must_use]
+    pub fn negotiate(preferences: &[Config], advertised: &[Config]) -> Option<Config> {
+        preferences
+            .iter()
+            .find(|config| advertised.contains(config))
+            .copied()
+    }
+
+    /// Choose the [`Config`] to use with a peer that advertised raw `(aead_id, kdf_id, kem_id)`
+    /// triples, as with [`Config::negotiate`]. Triples containing an id that does not identify
+    /// an algorithm supported by this build of the crate are skipped, as described in
+    /// [`Config::try_from_ids`].
+    #[must_use]
+    pub fn negotiate_ids(preferences: &[Config], advertised: &[(u16, u16, u16)]) -> Option<Config> {
+        let advertised = advertised
+            .iter()
+            .filter_map(|&(aead_id, kdf_id, kem_id)| {
+                Config::try_from_ids(aead_id, kdf_id, kem_id).ok()
+            })
+            .collect::<Vec<_>>();
+        Config::negotiate(preferences, &advertised)
+    }
 }
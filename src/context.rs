@@ -0,0 +1,706 @@
+use hpke::{
+    aead::{AeadCtxR, AeadCtxS},
+    danger::streaming_enc::{
+        create_receiver_context, create_sender_context, AeadKey, AeadNonce, ExporterSecret,
+    },
+    HpkeError, Serializable,
+};
+use zeroize::Zeroizing;
+
+use crate::{
+    deserialize_encapped_key, deserialize_private_key, deserialize_public_key,
+    key_schedule::{key_schedule, KeySchedule, Lengths, ModeInput},
+    open_error, seal_error, Aead, Config, Error,
+};
+
+/// A stateful HPKE encryption context, as described in [RFC9180§5.2][1].
+///
+/// Obtained from [`setup_sender`] or [`Config::setup_sender`]. Each call to
+/// [`SenderContext::seal`] advances the context's sequence number, so ciphertexts must either be
+/// opened by a [`ReceiverContext`] in the order in which they were sealed, or be conveyed along
+/// with their sequence number (see [`SenderContext::seal_with_seq`] and
+/// [`ReceiverContext::open_at`]).
+///
+/// [1]: https://www.rfc-editor.org/rfc/rfc9180.html#section-5.2
+pub struct SenderContext {
+    state: ContextState,
+}
+
+impl SenderContext {
+    /// Encrypt and authenticate `plaintext` with associated data `aad`, advancing the sequence
+    /// number.
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if the context was set up with the
+    /// [export-only](crate::Aead::ExportOnly) AEAD, if there is an error in encryption, or if the
+    /// context has reached its message limit.
+    pub fn seal(&mut self, plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>, Error> {
+        self.seal_with_seq(plaintext, aad)
+            .map(|(_, ciphertext)| ciphertext)
+    }
+
+    /// Encrypt and authenticate `plaintext` with associated data `aad`, advancing the sequence
+    /// number. Returns the sequence number the ciphertext was sealed at along with the
+    /// ciphertext, so that the recipient can open it out of order with
+    /// [`ReceiverContext::open_at`].
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if the context was set up with the
+    /// [export-only](crate::Aead::ExportOnly) AEAD, if there is an error in encryption, or if the
+    /// context has reached its message limit.
+    pub fn seal_with_seq(&mut self, plaintext: &[u8], aad: &[u8]) -> Result<(u64, Vec<u8>), Error> {
+        self.state.next(plaintext, aad, ContextState::seal_at)
+    }
+
+    /// the sequence number the next call to [`SenderContext::seal`] will use, or `None` if the
+    /// context has reached its message limit
+    #[must_use]
+    pub fn seq(&self) -> Option<u64> {
+        self.state.seq
+    }
+
+    /// Export a secret of `len` bytes from this context, bound to `exporter_context`, as described
+    /// in [RFC9180§5.3][1]. Exporting does not affect the sequence number.
+    ///
+    /// [1]: https://www.rfc-editor.org/rfc/rfc9180.html#section-5.3
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if `len` is too large for the selected
+    /// [`Kdf`](crate::Kdf).
+    pub fn export(&self, exporter_context: &[u8], len: usize) -> Result<Zeroizing<Vec<u8>>, Error> {
+        self.state.export(exporter_context, len)
+    }
+
+    /// the [`Config`] this context was set up with
+    #[must_use]
+    pub fn config(&self) -> Config {
+        self.state.config
+    }
+
+    /// Save this context's secrets, sequence number and [`Config`] into a versioned byte blob,
+    /// which can be passed to [`SenderContext::resume`] to continue sealing after a restart.
+    ///
+    /// The blob contains the context's secret key material and must be stored as carefully as a
+    /// private key. It is zeroized when dropped. A resumed context must never be used alongside
+    /// the context it was saved from, as they would seal different messages with the same nonce.
+    #[must_use]
+    pub fn save_state(&self) -> Zeroizing<Vec<u8>> {
+        self.state.save(Role::Sender)
+    }
+
+    /// Rebuild a [`SenderContext`] from a blob produced by [`SenderContext::save_state`].
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if:
+    ///
+    /// * the blob is [not a valid sender context state](Error::InvalidContextState)
+    /// * the blob was saved for a [different `Config`](Error::ContextConfigMismatch)
+    pub fn resume(config: &Config, state: &[u8]) -> Result<Self, Error> {
+        Ok(Self {
+            state: ContextState::resume(config, Role::Sender, state)?,
+        })
+    }
+}
+
+impl std::fmt::Debug for SenderContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SenderContext")
+            .field("config", &self.state.config)
+            .field("seq", &self.state.seq)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A stateful HPKE decryption context, as described in [RFC9180§5.2][1].
+///
+/// Obtained from [`setup_receiver`] or [`Config::setup_receiver`]. Each successful call to
+/// [`ReceiverContext::open`] advances the context's sequence number. Ciphertexts that arrive out
+/// of order can be opened at an explicit sequence number with [`ReceiverContext::open_at`].
+///
+/// [1]: https://www.rfc-editor.org/rfc/rfc9180.html#section-5.2
+pub struct ReceiverContext {
+    state: ContextState,
+}
+
+impl ReceiverContext {
+    /// Decrypt and authenticate `ciphertext` with associated data `aad`, advancing the sequence
+    /// number if successful.
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if the context was set up with the
+    /// [export-only](crate::Aead::ExportOnly) AEAD, if there is an error in decryption, or if the
+    /// context has reached its message limit.
+    pub fn open(&mut self, ciphertext: &[u8], aad: &[u8]) -> Result<Vec<u8>, Error> {
+        self.state
+            .next(ciphertext, aad, ContextState::open_at)
+            .map(|(_, plaintext)| plaintext)
+    }
+
+    /// Decrypt and authenticate `ciphertext` with associated data `aad`, using the nonce for
+    /// sequence number `seq`. This does not read or advance the context's own sequence number,
+    /// which allows ciphertexts to be opened out of order, for example when they are carried over
+    /// an unordered or lossy transport.
+    ///
+    /// This does not protect against replay: callers that open at explicit sequence numbers are
+    /// responsible for rejecting a sequence number they have already accepted.
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if the context was set up with the
+    /// [export-only](crate::Aead::ExportOnly) AEAD, if there is an error in decryption, or if
+    /// `seq` is outside the nonce space of the selected [`Aead`](crate::Aead).
+    pub fn open_at(&self, seq: u64, ciphertext: &[u8], aad: &[u8]) -> Result<Vec<u8>, Error> {
+        self.state.open_at(seq, ciphertext, aad)
+    }
+
+    /// the sequence number the next call to [`ReceiverContext::open`] will use, or `None` if the
+    /// context has reached its message limit
+    #[must_use]
+    pub fn seq(&self) -> Option<u64> {
+        self.state.seq
+    }
+
+    /// Export a secret of `len` bytes from this context, bound to `exporter_context`, as described
+    /// in [RFC9180§5.3][1]. Exporting does not affect the sequence number.
+    ///
+    /// [1]: https://www.rfc-editor.org/rfc/rfc9180.html#section-5.3
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if `len` is too large for the selected
+    /// [`Kdf`](crate::Kdf).
+    pub fn export(&self, exporter_context: &[u8], len: usize) -> Result<Zeroizing<Vec<u8>>, Error> {
+        self.state.export(exporter_context, len)
+    }
+
+    /// the [`Config`] this context was set up with
+    #[must_use]
+    pub fn config(&self) -> Config {
+        self.state.config
+    }
+
+    /// Save this context's secrets, sequence number and [`Config`] into a versioned byte blob,
+    /// which can be passed to [`ReceiverContext::resume`] to continue opening after a restart.
+    ///
+    /// The blob contains the context's secret key material and must be stored as carefully as a
+    /// private key. It is zeroized when dropped.
+    #[must_use]
+    pub fn save_state(&self) -> Zeroizing<Vec<u8>> {
+        self.state.save(Role::Receiver)
+    }
+
+    /// Rebuild a [`ReceiverContext`] from a blob produced by [`ReceiverContext::save_state`].
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if:
+    ///
+    /// * the blob is [not a valid receiver context state](Error::InvalidContextState)
+    /// * the blob was saved for a [different `Config`](Error::ContextConfigMismatch)
+    pub fn resume(config: &Config, state: &[u8]) -> Result<Self, Error> {
+        Ok(Self {
+            state: ContextState::resume(config, Role::Receiver, state)?,
+        })
+    }
+}
+
+impl std::fmt::Debug for ReceiverContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReceiverContext")
+            .field("config", &self.state.config)
+            .field("seq", &self.state.seq)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Set up a stateful Base mode HPKE encryption context.
+///
+/// `setup_sender` provides an interface to [`hpke::setup_sender`] that does not require compile
+/// time selection of an algorithm. Instead, the selected algorithm is provided through the
+/// [`Config`] passed as the first argument.
+///
+/// Returns the encapsulated key, which must be conveyed to the recipient, and the
+/// [`SenderContext`].
+///
+/// # Errors
+///
+/// This will return a `Result::Err` variant if:
+///
+/// * we are unable to deserialize the recipient public key
+/// * there is an error in key encapsultion
+/// * `info` is too long for the selected [`Kdf`](crate::Kdf)
+pub fn setup_sender(
+    config: &Config,
+    recipient_public_key: &[u8],
+    info: &[u8],
+) -> Result<(Vec<u8>, SenderContext), Error> {
+    let Config { aead, kdf, kem } = *config;
+    let setup = match_algo!(aead, kdf, kem, sender);
+    let (encapped_key, state) = setup(config, recipient_public_key, info)?;
+    Ok((encapped_key, SenderContext { state }))
+}
+
+/// Set up a stateful Base mode HPKE decryption context.
+///
+/// `setup_receiver` provides an interface to [`hpke::setup_receiver`] that does not require
+/// compile time selection of an algorithm. Instead, the selected algorithm is provided through
+/// the [`Config`] passed as the first argument.
+///
+/// # Errors
+///
+/// This will return a `Result::Err` variant if:
+///
+/// * we are unable to deserialize the private key or encapsulated key
+/// * there is an error in key decapsulation
+/// * `info` is too long for the selected [`Kdf`](crate::Kdf)
+pub fn setup_receiver(
+    config: &Config,
+    private_key: &[u8],
+    encapped_key: &[u8],
+    info: &[u8],
+) -> Result<ReceiverContext, Error> {
+    let Config { aead, kdf, kem } = *config;
+    let setup = match_algo!(aead, kdf, kem, receiver);
+    Ok(ReceiverContext {
+        state: setup(config, private_key, encapped_key, info)?,
+    })
+}
+
+/// The version of the [`ContextState`] serialization, which is laid out as:
+///
+/// ```text
+/// version: u8 || role: u8 || aead_id: u16 || kdf_id: u16 || kem_id: u16 ||
+/// has_seq: u8 || seq: u64 ||
+/// len: u16 || key || len: u16 || base_nonce || len: u16 || exporter_secret
+/// ```
+///
+/// with all integers big-endian.
+const STATE_VERSION: u8 = 1;
+
+/// Which side of a context a saved [`ContextState`] belongs to.
+#[derive(Clone, Copy)]
+#[repr(u8)]
+enum Role {
+    Sender = 0,
+    Receiver = 1,
+}
+
+/// An [`hpke`] sender or receiver context, erased over its algorithms.
+trait HpkeContext: Send + Sync {
+    /// seal or open `input`, advancing the context's own sequence number if successful
+    fn process(&mut self, input: &[u8], aad: &[u8]) -> Result<Vec<u8>, Error>;
+
+    fn export(&self, exporter_context: &[u8], out: &mut [u8]) -> Result<(), HpkeError>;
+}
+
+impl<AeadT, KdfT, KemT> HpkeContext for AeadCtxS<AeadT, KdfT, KemT>
+where
+    AeadT: hpke::aead::Aead,
+    KdfT: hpke::kdf::Kdf,
+    KemT: hpke::kem::Kem,
+    Self: Send + Sync,
+{
+    fn process(&mut self, plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>, Error> {
+        Ok(self.seal(plaintext, aad)?)
+    }
+
+    fn export(&self, exporter_context: &[u8], out: &mut [u8]) -> Result<(), HpkeError> {
+        AeadCtxS::export(self, exporter_context, out)
+    }
+}
+
+impl<AeadT, KdfT, KemT> HpkeContext for AeadCtxR<AeadT, KdfT, KemT>
+where
+    AeadT: hpke::aead::Aead,
+    KdfT: hpke::kdf::Kdf,
+    KemT: hpke::kem::Kem,
+    Self: Send + Sync,
+{
+    fn process(&mut self, ciphertext: &[u8], aad: &[u8]) -> Result<Vec<u8>, Error> {
+        self.open(ciphertext, aad)
+            .map_err(open_error::<AeadT, KemT>)
+    }
+
+    fn export(&self, exporter_context: &[u8], out: &mut [u8]) -> Result<(), HpkeError> {
+        AeadCtxR::export(self, exporter_context, out)
+    }
+}
+
+/// [`ContextState::seal_at`] or [`ContextState::open_at`]
+type ProcessAt = fn(&ContextState, u64, &[u8], &[u8]) -> Result<Vec<u8>, Error>;
+
+/// The state shared by [`SenderContext`] and [`ReceiverContext`]: the secrets derived by the key
+/// schedule, the next sequence number, which is `None` once the message limit is reached, and
+/// the [`hpke`] context, which is `None` if the state was resumed.
+///
+/// A context that was set up by [`setup_sender`] or [`setup_receiver`] seals, opens and exports
+/// with the [`hpke`] context, unless its [`Aead`] is [export-only](Aead::ExportOnly): [`hpke`]'s
+/// key schedule derives a placeholder nonce for its export-only AEAD, which overflows the buffer
+/// of its one-stage KDFs, so export-only contexts export with the secrets instead. The secrets are
+/// only used to open at an explicit sequence number, and to save and resume the state, as [`hpke`]
+/// contexts can neither be started at a sequence number other than zero nor reveal their secrets.
+struct ContextState {
+    config: Config,
+    schedule: KeySchedule,
+    seq: Option<u64>,
+    hpke: Option<Box<dyn HpkeContext>>,
+}
+
+impl ContextState {
+    fn new(config: Config, schedule: KeySchedule, hpke: Option<Box<dyn HpkeContext>>) -> Self {
+        Self {
+            config,
+            schedule,
+            seq: Some(0),
+            hpke,
+        }
+    }
+
+    fn save(&self, role: Role) -> Zeroizing<Vec<u8>> {
+        let KeySchedule {
+            key,
+            base_nonce,
+            exporter_secret,
+        } = &self.schedule;
+        let mut state = Zeroizing::new(Vec::with_capacity(
+            23 + key.len() + base_nonce.len() + exporter_secret.len(),
+        ));
+
+        state.extend_from_slice(&[STATE_VERSION, role as u8]);
+        for id in [
+            u16::from(self.config.aead),
+            u16::from(self.config.kdf),
+            u16::from(self.config.kem),
+        ] {
+            state.extend_from_slice(&id.to_be_bytes());
+        }
+        state.push(u8::from(self.seq.is_some()));
+        state.extend_from_slice(&self.seq.unwrap_or_default().to_be_bytes());
+        for secret in [key, base_nonce, exporter_secret] {
+            let len = u16::try_from(secret.len()).expect("secret length fits in a u16");
+            state.extend_from_slice(&len.to_be_bytes());
+            state.extend_from_slice(secret);
+        }
+
+        state
+    }
+
+    fn resume(config: &Config, role: Role, state: &[u8]) -> Result<Self, Error> {
+        let mut reader = StateReader(state);
+
+        if reader.take(2)? != [STATE_VERSION, role as u8] {
+            return Err(Error::InvalidContextState);
+        }
+
+        let (aead_id, kdf_id, kem_id) = (reader.u16()?, reader.u16()?, reader.u16()?);
+        let saved_config = Config::try_from_ids(aead_id, kdf_id, kem_id)
+            .map_err(|_| Error::InvalidContextState)?;
+        if saved_config != *config {
+            return Err(Error::ContextConfigMismatch {
+                expected: *config,
+                found: saved_config,
+            });
+        }
+
+        let seq = match (reader.take(1)?[0], reader.u64()?) {
+            (0, 0) => None,
+            (1, seq) => Some(seq),
+            _ => return Err(Error::InvalidContextState),
+        };
+
+        let Config { aead, kdf, kem } = *config;
+        let lengths = match_algo!(aead, kdf, kem, lengths)();
+        let schedule = KeySchedule {
+            key: reader.secret(lengths.key)?,
+            base_nonce: reader.secret(lengths.base_nonce)?,
+            exporter_secret: reader.secret(lengths.exporter_secret)?,
+        };
+
+        if !reader.0.is_empty() {
+            return Err(Error::InvalidContextState);
+        }
+
+        Ok(Self {
+            config: *config,
+            schedule,
+            seq,
+            hpke: None,
+        })
+    }
+
+    fn next_seq(&self) -> Result<u64, Error> {
+        self.seq.ok_or(Error::Hpke(HpkeError::MessageLimitReached))
+    }
+
+    fn advance(&mut self) {
+        self.seq = self.seq.and_then(|seq| seq.checked_add(1));
+    }
+
+    /// seal or open `input` at the next sequence number and advance it, with the [`hpke`] context,
+    /// or with `at` if the state was resumed
+    fn next(&mut self, input: &[u8], aad: &[u8], at: ProcessAt) -> Result<(u64, Vec<u8>), Error> {
+        let seq = self.next_seq()?;
+        let output = match &mut self.hpke {
+            Some(hpke) => hpke.process(input, aad)?,
+            None => at(self, seq, input, aad)?,
+        };
+        self.advance();
+        Ok((seq, output))
+    }
+
+    /// RFC9180§5.2 reserves the last nonce for each key, so `seq` must be less than
+    /// `2^(8 * Nn) - 1`. Every encrypting [`Aead`] has a nonce of at least eight bytes, which
+    /// leaves only `u64::MAX` to reject.
+    fn check_seq(&self, seq: u64) -> Result<(), Error> {
+        self.config.aead.check_encryption()?;
+        if seq == u64::MAX && self.schedule.base_nonce.len() <= 8 {
+            Err(Error::Hpke(HpkeError::MessageLimitReached))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn seal_at(&self, seq: u64, plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>, Error> {
+        self.check_seq(seq)?;
+        let Config { aead, kdf, kem } = self.config;
+        let seal = match_algo!(aead, kdf, kem, seal_at);
+        Ok(seal(&self.schedule, seq, plaintext, aad)?)
+    }
+
+    fn open_at(&self, seq: u64, ciphertext: &[u8], aad: &[u8]) -> Result<Vec<u8>, Error> {
+        self.check_seq(seq)?;
+        let Config { aead, kdf, kem } = self.config;
+        let open = match_algo!(aead, kdf, kem, open_at);
+        open(&self.schedule, seq, ciphertext, aad)
+    }
+
+    fn export(&self, exporter_context: &[u8], len: usize) -> Result<Zeroizing<Vec<u8>>, Error> {
+        let mut secret = Zeroizing::new(vec![0; len]);
+        if let Some(hpke) = &self.hpke {
+            hpke.export(exporter_context, &mut secret)?;
+        } else {
+            let Config { aead, kdf, kem } = self.config;
+            let export = match_algo!(aead, kdf, kem, export);
+            export(&self.schedule, exporter_context, &mut secret)?;
+        }
+        Ok(secret)
+    }
+}
+
+/// A cursor over a saved [`ContextState`].
+struct StateReader<'a>(&'a [u8]);
+
+impl<'a> StateReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        let (taken, rest) = self
+            .0
+            .split_at_checked(len)
+            .ok_or(Error::InvalidContextState)?;
+        self.0 = rest;
+        Ok(taken)
+    }
+
+    fn u16(&mut self) -> Result<u16, Error> {
+        Ok(u16::from_be_bytes(
+            self.take(2)?.try_into().expect("took two bytes"),
+        ))
+    }
+
+    fn u64(&mut self) -> Result<u64, Error> {
+        Ok(u64::from_be_bytes(
+            self.take(8)?.try_into().expect("took eight bytes"),
+        ))
+    }
+
+    /// reads a length-prefixed secret, which must be `expected_len` bytes long
+    fn secret(&mut self, expected_len: usize) -> Result<Zeroizing<Vec<u8>>, Error> {
+        if usize::from(self.u16()?) != expected_len {
+            return Err(Error::InvalidContextState);
+        }
+        Ok(Zeroizing::new(self.take(expected_len)?.to_vec()))
+    }
+}
+
+// `match_algo!` always supplies a kem, even though the lengths do not depend on it
+#[allow(clippy::extra_unused_type_parameters)]
+fn lengths<AeadT, KdfT, KemT>() -> Lengths
+where
+    AeadT: hpke::aead::Aead,
+    KdfT: hpke::kdf::Kdf,
+    KemT: hpke::kem::Kem,
+{
+    Lengths {
+        key: AeadKey::<AeadT>::default().0.len(),
+        // the export-only AEAD has a placeholder nonce size, but no base nonce in the key schedule
+        base_nonce: if AeadT::AEAD_ID == u16::from(Aead::ExportOnly) {
+            0
+        } else {
+            AeadNonce::<AeadT>::default().0.len()
+        },
+        exporter_secret: ExporterSecret::<KdfT>::default().0.len(),
+    }
+}
+
+// The following build `hpke::danger` contexts from the key schedule secrets, for opening at an
+// explicit sequence number, for resumed states and for export-only contexts. They are the only
+// users of `hpke::danger`, which is why the `hpke` patch version is pinned.
+
+fn aead_key<AeadT: hpke::aead::Aead>(schedule: &KeySchedule) -> AeadKey<AeadT> {
+    let mut key = AeadKey::default();
+    key.0.copy_from_slice(&schedule.key);
+    key
+}
+
+/// the nonce for `seq`, as a base nonce for an [`hpke`] context at its own sequence number zero
+fn aead_nonce<AeadT: hpke::aead::Aead>(schedule: &KeySchedule, seq: u64) -> AeadNonce<AeadT> {
+    let mut nonce = AeadNonce::default();
+    for (byte, base) in nonce.0.iter_mut().zip(schedule.base_nonce.iter()) {
+        *byte = *base;
+    }
+    let seq_start = nonce.0.len().saturating_sub(8);
+    for (byte, seq) in nonce.0[seq_start..].iter_mut().zip(seq.to_be_bytes()) {
+        *byte ^= seq;
+    }
+    nonce
+}
+
+fn exporter_secret<KdfT: hpke::kdf::Kdf>(schedule: &KeySchedule) -> ExporterSecret<KdfT> {
+    let mut secret = ExporterSecret::default();
+    secret.0.copy_from_slice(&schedule.exporter_secret);
+    secret
+}
+
+fn seal_at<AeadT, KdfT, KemT>(
+    schedule: &KeySchedule,
+    seq: u64,
+    plaintext: &[u8],
+    aad: &[u8],
+) -> Result<Vec<u8>, HpkeError>
+where
+    AeadT: hpke::aead::Aead,
+    KdfT: hpke::kdf::Kdf,
+    KemT: hpke::kem::Kem,
+{
+    create_sender_context::<AeadT, KdfT, KemT>(
+        &aead_key(schedule),
+        aead_nonce(schedule, seq),
+        exporter_secret(schedule),
+    )
+    .seal(plaintext, aad)
+}
+
+fn open_at<AeadT, KdfT, KemT>(
+    schedule: &KeySchedule,
+    seq: u64,
+    ciphertext: &[u8],
+    aad: &[u8],
+) -> Result<Vec<u8>, Error>
+where
+    AeadT: hpke::aead::Aead,
+    KdfT: hpke::kdf::Kdf,
+    KemT: hpke::kem::Kem,
+{
+    create_receiver_context::<AeadT, KdfT, KemT>(
+        &aead_key(schedule),
+        aead_nonce(schedule, seq),
+        exporter_secret(schedule),
+    )
+    .open(ciphertext, aad)
+    .map_err(open_error::<AeadT, KemT>)
+}
+
+fn export<AeadT, KdfT, KemT>(
+    schedule: &KeySchedule,
+    exporter_context: &[u8],
+    out: &mut [u8],
+) -> Result<(), HpkeError>
+where
+    AeadT: hpke::aead::Aead,
+    KdfT: hpke::kdf::Kdf,
+    KemT: hpke::kem::Kem,
+{
+    create_receiver_context::<AeadT, KdfT, KemT>(
+        &aead_key(schedule),
+        aead_nonce(schedule, 0),
+        exporter_secret(schedule),
+    )
+    .export(exporter_context, out)
+}
+
+/// encapsulate to `recipient_public_key`, and run the key schedule both to get the secrets of the
+/// [`ContextState`] and, with [`hpke::kdf::Kdf::combine_secrets`], to set up its [`hpke`] context
+///
+/// `combine_secrets` is the key schedule that [`hpke::setup_sender`] runs after encapsulating,
+/// which is called directly because the shared secret is also needed for our own key schedule.
+fn sender<AeadT, KdfT, KemT>(
+    config: &Config,
+    recipient_public_key: &[u8],
+    info: &[u8],
+) -> Result<(Vec<u8>, ContextState), Error>
+where
+    AeadT: hpke::aead::Aead,
+    KdfT: hpke::kdf::Kdf,
+    KemT: hpke::kem::Kem,
+    AeadCtxS<AeadT, KdfT, KemT>: Send + Sync + 'static,
+{
+    let recipient_public_key = deserialize_public_key::<KemT>(recipient_public_key)?;
+    let (shared_secret, encapped_key) =
+        KemT::encap(&recipient_public_key, None).map_err(seal_error::<KemT>)?;
+    let schedule = key_schedule(
+        config,
+        &ModeInput::BASE,
+        &shared_secret.0,
+        info,
+        lengths::<AeadT, KdfT, KemT>(),
+    )?;
+    let hpke = (config.aead != Aead::ExportOnly).then(|| {
+        let hpke: AeadCtxS<AeadT, KdfT, KemT> =
+            KdfT::combine_secrets(&hpke::OpModeS::Base, shared_secret, info).into();
+        Box::new(hpke) as Box<dyn HpkeContext>
+    });
+
+    Ok((
+        encapped_key.to_bytes().to_vec(),
+        ContextState::new(*config, schedule, hpke),
+    ))
+}
+
+/// decapsulate `encapped_key`, and set up the [`ContextState`] as in [`sender`]
+fn receiver<AeadT, KdfT, KemT>(
+    config: &Config,
+    private_key: &[u8],
+    encapped_key: &[u8],
+    info: &[u8],
+) -> Result<ContextState, Error>
+where
+    AeadT: hpke::aead::Aead,
+    KdfT: hpke::kdf::Kdf,
+    KemT: hpke::kem::Kem,
+    AeadCtxR<AeadT, KdfT, KemT>: Send + Sync + 'static,
+{
+    let shared_secret = KemT::decap(
+        &deserialize_private_key::<KemT>(private_key)?,
+        None,
+        &deserialize_encapped_key::<KemT>(encapped_key)?,
+    )
+    .map_err(open_error::<AeadT, KemT>)?;
+    let schedule = key_schedule(
+        config,
+        &ModeInput::BASE,
+        &shared_secret.0,
+        info,
+        lengths::<AeadT, KdfT, KemT>(),
+    )?;
+    let hpke = (config.aead != Aead::ExportOnly).then(|| {
+        let hpke: AeadCtxR<AeadT, KdfT, KemT> =
+            KdfT::combine_secrets(&hpke::OpModeR::Base, shared_secret, info).into();
+        Box::new(hpke) as Box<dyn HpkeContext>
+    });
+
+    Ok(ContextState::new(*config, schedule, hpke))
+}
@@ -0,0 +1,360 @@
+use std::convert::Infallible;
+
+use minicbor::{data::Type, Decoder, Encoder};
+use zeroize::Zeroizing;
+
+use crate::{keypair::public_key_from_private, Error, Kem, Keypair, PublicKey};
+
+// COSE_Key parameter labels, from RFC9052 and RFC9053
+const KTY: i64 = 1;
+#[cfg(feature = "mlkem")]
+const ALG: i64 = 3;
+#[cfg(any(feature = "nistp", feature = "x25519"))]
+const CRV: i64 = -1;
+#[cfg(any(feature = "nistp", feature = "x25519"))]
+const X: i64 = -2;
+#[cfg(feature = "nistp")]
+const Y: i64 = -3;
+#[cfg(any(feature = "nistp", feature = "x25519"))]
+const D: i64 = -4;
+// AKP key parameter labels, from draft-ietf-cose-dilithium
+#[cfg(feature = "mlkem")]
+const PUB: i64 = -1;
+#[cfg(feature = "mlkem")]
+const PRIV: i64 = -2;
+
+/// The COSE algorithm values of ML-KEM-768 and ML-KEM-1024 requested by
+/// [draft-ietf-jose-pqc-kem](https://datatracker.ietf.org/doc/draft-ietf-jose-pqc-kem/). They
+/// are provisional until IANA registers them.
+#[cfg(feature = "mlkem")]
+const ML_KEM_768: i64 = -83;
+#[cfg(feature = "mlkem")]
+const ML_KEM_1024: i64 = -84;
+
+/// The COSE key type and curve or algorithm of a [`Kem`].
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum KeyType {
+    /// an `OKP` (1) key on the identified curve, as described in
+    /// [RFC9053](https://www.rfc-editor.org/rfc/rfc9053#section-7.2)
+    #[cfg(feature = "x25519")]
+    Okp(i64),
+
+    /// an `EC2` (2) key on the identified curve, as described in
+    /// [RFC9053](https://www.rfc-editor.org/rfc/rfc9053#section-7.1)
+    #[cfg(feature = "nistp")]
+    Ec2(i64),
+
+    /// an `AKP` (7) key for the identified algorithm, as described in
+    /// [draft-ietf-cose-dilithium](https://datatracker.ietf.org/doc/draft-ietf-cose-dilithium/)
+    /// and [draft-ietf-jose-pqc-kem](https://datatracker.ietf.org/doc/draft-ietf-jose-pqc-kem/)
+    #[cfg(feature = "mlkem")]
+    Akp(i64),
+}
+
+/// The [`Kem`]s with COSE key types. The hybrid [`Kem`]s have none: no COSE draft defines a key
+/// type or algorithm value for them yet.
+const KEY_TYPES: &[(Kem, KeyType)] = &[
+    #[cfg(feature = "nistp")]
+    (Kem::DhP256HkdfSha256, KeyType::Ec2(1)),
+    #[cfg(feature = "nistp")]
+    (Kem::DhP384HkdfSha384, KeyType::Ec2(2)),
+    #[cfg(feature = "nistp")]
+    (Kem::DhP521HkdfSha512, KeyType::Ec2(3)),
+    #[cfg(feature = "x25519")]
+    (Kem::X25519HkdfSha256, KeyType::Okp(4)),
+    #[cfg(feature = "mlkem")]
+    (Kem::MlKem768, KeyType::Akp(ML_KEM_768)),
+    #[cfg(feature = "mlkem")]
+    (Kem::MlKem1024, KeyType::Akp(ML_KEM_1024)),
+];
+
+impl KeyType {
+    #[cfg(feature = "x25519")]
+    const OKP: i64 = 1;
+    #[cfg(feature = "nistp")]
+    const EC2: i64 = 2;
+    #[cfg(feature = "mlkem")]
+    const AKP: i64 = 7;
+
+    fn for_kem(kem: Kem) -> Result<Self, Error> {
+        KEY_TYPES
+            .iter()
+            .find(|(candidate, _)| *candidate == kem)
+            .map(|(_, key_type)| *key_type)
+            .ok_or(Error::UnsupportedKeyEncoding { kem })
+    }
+}
+
+/// A decoded CBOR value of a COSE_Key parameter. Only integers and byte strings are used by the
+/// parameters we read.
+#[derive(Clone, Copy)]
+enum Value<'a> {
+    Int(i64),
+    Bytes(&'a [u8]),
+    Other,
+}
+
+/// The integer-labelled parameters of a decoded COSE_Key.
+struct CoseKey<'a>(Vec<(i64, Value<'a>)>);
+
+impl<'a> CoseKey<'a> {
+    fn decode(bytes: &'a [u8]) -> Result<Self, Error> {
+        Self::decode_map(&mut Decoder::new(bytes)).map_err(|_| Error::InvalidKeyEncoding)
+    }
+
+    fn decode_map(decoder: &mut Decoder<'a>) -> Result<Self, minicbor::decode::Error> {
+        let invalid = || minicbor::decode::Error::message("invalid COSE_Key");
+        let len = decoder.map()?.ok_or_else(invalid)?;
+        let mut parameters = Vec::new();
+        for _ in 0..len {
+            let label = match decoder.datatype()? {
+                Type::String => {
+                    // text labels are not used by any parameter we read
+                    decoder.skip()?;
+                    decoder.skip()?;
+                    continue;
+                }
+                _ => decoder.i64()?,
+            };
+            let value = match decoder.datatype()? {
+                Type::U8
+                | Type::U16
+                | Type::U32
+                | Type::U64
+                | Type::I8
+                | Type::I16
+                | Type::I32
+                | Type::I64 => Value::Int(decoder.i64()?),
+                Type::Bytes => Value::Bytes(decoder.bytes()?),
+                _ => {
+                    decoder.skip()?;
+                    Value::Other
+                }
+            };
+            if parameters.iter().any(|(existing, _)| *existing == label) {
+                return Err(invalid());
+            }
+            parameters.push((label, value));
+        }
+        if decoder.position() != decoder.input().len() {
+            return Err(invalid());
+        }
+        Ok(Self(parameters))
+    }
+
+    fn get(&self, label: i64) -> Option<Value<'a>> {
+        self.0
+            .iter()
+            .find(|(candidate, _)| *candidate == label)
+            .map(|(_, value)| *value)
+    }
+
+    fn int(&self, label: i64) -> Result<Option<i64>, Error> {
+        match self.get(label) {
+            None => Ok(None),
+            Some(Value::Int(value)) => Ok(Some(value)),
+            Some(_) => Err(Error::InvalidKeyEncoding),
+        }
+    }
+
+    fn bytes(&self, label: i64) -> Result<Option<&'a [u8]>, Error> {
+        match self.get(label) {
+            None => Ok(None),
+            Some(Value::Bytes(value)) => Ok(Some(value)),
+            Some(_) => Err(Error::InvalidKeyEncoding),
+        }
+    }
+
+    fn kem(&self) -> Result<(Kem, KeyType), Error> {
+        let key_type = match self.int(KTY)? {
+            #[cfg(feature = "x25519")]
+            Some(KeyType::OKP) => self.int(CRV)?.map(KeyType::Okp),
+            #[cfg(feature = "nistp")]
+            Some(KeyType::EC2) => self.int(CRV)?.map(KeyType::Ec2),
+            #[cfg(feature = "mlkem")]
+            Some(KeyType::AKP) => self.int(ALG)?.map(KeyType::Akp),
+            _ => None,
+        }
+        .ok_or(Error::InvalidKeyEncoding)?;
+        KEY_TYPES
+            .iter()
+            .find(|(_, candidate)| *candidate == key_type)
+            .map(|(kem, _)| (*kem, key_type))
+            .ok_or(Error::InvalidKeyEncoding)
+    }
+
+    /// the public key, if the COSE_Key has one
+    fn public_key(&self, key_type: KeyType) -> Result<Option<Vec<u8>>, Error> {
+        Ok(match key_type {
+            #[cfg(feature = "x25519")]
+            KeyType::Okp(_) => self.bytes(X)?.map(<[u8]>::to_vec),
+
+            #[cfg(feature = "nistp")]
+            KeyType::Ec2(_) => match (self.bytes(X)?, self.bytes(Y)?) {
+                (Some(x), Some(y)) if x.len() == y.len() => Some([&[0x04], x, y].concat()),
+                (None, None) => None,
+                _ => return Err(Error::InvalidKeyEncoding),
+            },
+
+            #[cfg(feature = "mlkem")]
+            KeyType::Akp(_) => self.bytes(PUB)?.map(<[u8]>::to_vec),
+        })
+    }
+
+    /// the private key, if the COSE_Key has one
+    fn private_key(&self, key_type: KeyType) -> Result<Option<&'a [u8]>, Error> {
+        match key_type {
+            #[cfg(feature = "x25519")]
+            KeyType::Okp(_) => self.bytes(D),
+
+            #[cfg(feature = "nistp")]
+            KeyType::Ec2(_) => self.bytes(D),
+
+            #[cfg(feature = "mlkem")]
+            KeyType::Akp(_) => self.bytes(PRIV),
+        }
+    }
+}
+
+/// encode a COSE_Key, with its parameters in the deterministic order described in
+/// [RFC9052§9](https://www.rfc-editor.org/rfc/rfc9052#section-9)
+fn encode(kem: Kem, public_key: &[u8], private_key: Option<&[u8]>) -> Result<Vec<u8>, Error> {
+    let key_type = KeyType::for_kem(kem)?;
+    // reserve enough space up front that no copies of the private key are left behind by
+    // reallocation
+    let capacity = 32 + public_key.len() + private_key.map_or(0, <[u8]>::len);
+    let mut encoder = Encoder::new(Vec::with_capacity(capacity));
+    encode_parameters(&mut encoder, key_type, public_key, private_key)
+        .expect("writing to a Vec cannot fail");
+    Ok(encoder.into_writer())
+}
+
+fn encode_parameters(
+    encoder: &mut Encoder<Vec<u8>>,
+    key_type: KeyType,
+    public_key: &[u8],
+    private_key: Option<&[u8]>,
+) -> Result<(), minicbor::encode::Error<Infallible>> {
+    let private_len = u64::from(private_key.is_some());
+    let private_label = match key_type {
+        #[cfg(feature = "x25519")]
+        KeyType::Okp(crv) => {
+            encoder.map(3 + private_len)?;
+            encoder.i64(KTY)?.i64(KeyType::OKP)?;
+            encoder.i64(CRV)?.i64(crv)?;
+            encoder.i64(X)?.bytes(public_key)?;
+            D
+        }
+
+        #[cfg(feature = "nistp")]
+        KeyType::Ec2(crv) => {
+            // an uncompressed point: 0x04 || x || y
+            let (x, y) = public_key[1..].split_at((public_key.len() - 1) / 2);
+            encoder.map(4 + private_len)?;
+            encoder.i64(KTY)?.i64(KeyType::EC2)?;
+            encoder.i64(CRV)?.i64(crv)?;
+            encoder.i64(X)?.bytes(x)?;
+            encoder.i64(Y)?.bytes(y)?;
+            D
+        }
+
+        #[cfg(feature = "mlkem")]
+        KeyType::Akp(alg) => {
+            encoder.map(3 + private_len)?;
+            encoder.i64(KTY)?.i64(KeyType::AKP)?;
+            encoder.i64(ALG)?.i64(alg)?;
+            encoder.i64(PUB)?.bytes(public_key)?;
+            PRIV
+        }
+    };
+    if let Some(private_key) = private_key {
+        encoder.i64(private_label)?.bytes(private_key)?;
+    }
+    Ok(())
+}
+
+impl Keypair {
+    /// Encode this [`Keypair`] as a private COSE_Key for `kem`, as described in
+    /// [RFC9052§7](https://www.rfc-editor.org/rfc/rfc9052#section-7). The public key is included.
+    ///
+    /// Elliptic curve [`Kem`]s use the `OKP` and `EC2` key types from
+    /// [RFC9053](https://www.rfc-editor.org/rfc/rfc9053#section-7). ML-KEM uses the `AKP` key type
+    /// from [draft-ietf-cose-dilithium](https://datatracker.ietf.org/doc/draft-ietf-cose-dilithium/),
+    /// with the algorithm values and 64 byte seed private key of
+    /// [draft-ietf-jose-pqc-kem](https://datatracker.ietf.org/doc/draft-ietf-jose-pqc-kem/). No
+    /// COSE draft covers the hybrid [`Kem`]s yet, so they cannot be encoded.
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if:
+    ///
+    /// * the keypair is not a valid keypair for `kem`
+    /// * `kem` has no COSE key type ([`Error::UnsupportedKeyEncoding`])
+    pub fn to_cose_key(&self, kem: Kem) -> Result<Zeroizing<Vec<u8>>, Error> {
+        self.validate(kem)?;
+        encode(kem, &self.public_key, Some(&self.private_key)).map(Zeroizing::new)
+    }
+
+    /// Decode a private COSE_Key, returning the [`Kem`] identified by its key type and the
+    /// [`Keypair`]. See [`Keypair::to_cose_key`] for the supported key types.
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if:
+    ///
+    /// * the COSE_Key is malformed, is missing parameters, or does not identify a supported [`Kem`]
+    /// * we are unable to deserialize the private key
+    /// * the COSE_Key includes a public key that does not match the private key
+    pub fn from_cose_key(cose_key: &[u8]) -> Result<(Kem, Self), Error> {
+        let cose_key = CoseKey::decode(cose_key)?;
+        let (kem, key_type) = cose_key.kem()?;
+        let private_key = cose_key
+            .private_key(key_type)?
+            .ok_or(Error::InvalidKeyEncoding)?;
+        let public_key = public_key_from_private(kem, private_key)?;
+        if cose_key
+            .public_key(key_type)?
+            .is_some_and(|encoded| encoded != public_key)
+        {
+            return Err(Error::KeypairMismatch);
+        }
+        Ok((
+            kem,
+            Self {
+                private_key: Zeroizing::new(private_key.to_vec()),
+                public_key,
+            },
+        ))
+    }
+}
+
+impl PublicKey {
+    /// Encode this [`PublicKey`] as a public COSE_Key. See [`Keypair::to_cose_key`] for the key
+    /// types used.
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if the [`Kem`] has no COSE key type
+    /// ([`Error::UnsupportedKeyEncoding`]).
+    pub fn to_cose_key(&self) -> Result<Vec<u8>, Error> {
+        encode(self.kem(), self.as_bytes(), None)
+    }
+
+    /// Decode the public key from a public or private COSE_Key, taking its [`Kem`] from the key
+    /// type.
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if:
+    ///
+    /// * the COSE_Key is malformed, is missing parameters, or does not identify a supported [`Kem`]
+    /// * we are unable to deserialize the public key
+    pub fn from_cose_key(cose_key: &[u8]) -> Result<Self, Error> {
+        let cose_key = CoseKey::decode(cose_key)?;
+        let (kem, key_type) = cose_key.kem()?;
+        let public_key = cose_key
+            .public_key(key_type)?
+            .ok_or(Error::InvalidKeyEncoding)?;
+        Self::new(kem, &public_key)
+    }
+}
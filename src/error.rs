@@ -0,0 +1,218 @@
+use hpke::HpkeError;
+
+use crate::{Aead, Config, IdLookupError, Kdf, Kem, Mode, PolicyViolation};
+
+/// An error from one of this crate's interfaces.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// A public key, such as the recipient public key when sealing, could not be deserialized
+    /// for the selected [`Kem`], or cannot be encapsulated to.
+    InvalidPublicKey {
+        /// the [`Kem`] that was selected
+        kem: Kem,
+    },
+
+    /// A private key could not be deserialized for the selected [`Kem`].
+    InvalidPrivateKey {
+        /// the [`Kem`] that was selected
+        kem: Kem,
+    },
+
+    /// An encapsulated key could not be deserialized or decapsulated for the selected [`Kem`].
+    InvalidEncappedKey {
+        /// the [`Kem`] that was selected
+        kem: Kem,
+    },
+
+    /// A ciphertext could not be decrypted with the selected [`Aead`]: it was altered, or was
+    /// sealed for a different key, info string, associated data or sequence number.
+    DecryptionFailed {
+        /// the [`Aead`] that was selected
+        aead: Aead,
+    },
+
+    /// An algorithm id does not identify an [`Aead`], [`Kdf`](crate::Kdf) or [`Kem`] supported by
+    /// this build of the crate.
+    UnsupportedAlgorithm(IdLookupError),
+
+    /// A string is not a [`Config`] of three algorithm names separated by `/` or `:`.
+    InvalidConfigString,
+
+    /// A byte string is not an RFC9180 `suite_id`, as produced by [`Config::suite_id`].
+    InvalidSuiteId,
+
+    /// A [`Config`] is not allowed by a [`Policy`](crate::Policy).
+    PolicyViolation(PolicyViolation),
+
+    /// The selected [`Kem`] cannot be used with the requested [`Mode`], because it does not
+    /// provide `AuthEncap` and `AuthDecap`.
+    UnsupportedMode {
+        /// the [`Kem`] that was selected
+        kem: Kem,
+        /// the [`Mode`] that was requested
+        mode: Mode,
+    },
+
+    /// The selected [`Aead`] is [export-only](crate::Aead::ExportOnly), and cannot be used to
+    /// seal or open messages.
+    ExportOnly {
+        /// the [`Aead`] that was selected
+        aead: Aead,
+    },
+
+    /// The info string, pre-shared key or pre-shared key id is longer than the selected [`Kdf`]
+    /// can take: the one-stage SHAKE and TurboSHAKE KDFs allow at most 65535 bytes each.
+    KeyScheduleInputTooLong {
+        /// the [`Kdf`] that was selected
+        kdf: Kdf,
+    },
+
+    /// A pre-shared key is shorter than [`MIN_PSK_LEN`](crate::MIN_PSK_LEN) bytes, or its id is
+    /// empty, as forbidden by
+    /// [RFC9180§5.1.2](https://www.rfc-editor.org/rfc/rfc9180.html#section-5.1.2).
+    InvalidPsk {
+        /// the [`Kdf`] that was selected
+        kdf: Kdf,
+    },
+
+    /// The input keying material given to [`derive_keypair`](crate::derive_keypair) is longer
+    /// than the selected [`Kem`] can take: the post-quantum and hybrid KEMs allow at most 65535
+    /// bytes.
+    InvalidIkm {
+        /// the [`Kem`] that was selected
+        kem: Kem,
+    },
+
+    /// The public key of a [`Keypair`](crate::Keypair) does not correspond to its private key.
+    KeypairMismatch,
+
+    /// A typed key was created for a different [`Kem`] than the one it is being used with.
+    KemMismatch {
+        /// the [`Kem`] of the [`Config`] the key was used with
+        expected: Kem,
+        /// the [`Kem`] the key was created for
+        found: Kem,
+    },
+
+    /// An encoded key is malformed, or its algorithm does not identify a supported [`Kem`].
+    InvalidKeyEncoding,
+
+    /// The selected [`Kem`] has no standard identifier in the requested key encoding.
+    UnsupportedKeyEncoding {
+        /// the [`Kem`] that was selected
+        kem: Kem,
+    },
+
+    /// A saved context state could not be resumed, because it is malformed, was saved by an
+    /// unsupported version of this crate, or was saved from the other side of a context.
+    InvalidContextState,
+
+    /// A saved context state was created for a different [`Config`] than the one it is being
+    /// resumed with.
+    ContextConfigMismatch {
+        /// the [`Config`] the context was to be resumed with
+        expected: Config,
+        /// the [`Config`] the context state was saved with
+        found: Config,
+    },
+
+    /// A stream being opened by an [`OpeningReader`](crate::OpeningReader) is malformed, or has
+    /// data following its final chunk.
+    MalformedStream,
+
+    /// An error from the underlying [`hpke`] crate.
+    Hpke(HpkeError),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::InvalidPublicKey { kem } => write!(f, "invalid {kem} public key"),
+            Error::InvalidPrivateKey { kem } => write!(f, "invalid {kem} private key"),
+            Error::InvalidEncappedKey { kem } => write!(f, "invalid {kem} encapsulated key"),
+            Error::DecryptionFailed { aead } => {
+                write!(f, "{aead} decryption failed")
+            }
+            Error::UnsupportedAlgorithm(e) => write!(f, "unsupported algorithm: {e}"),
+            Error::InvalidConfigString => f.write_str("invalid config string"),
+            Error::InvalidSuiteId => f.write_str("invalid suite id"),
+            Error::PolicyViolation(e) => write!(f, "policy violation: {e}"),
+            Error::UnsupportedMode { kem, mode } => {
+                write!(f, "{kem} does not support {mode:?} mode")
+            }
+            Error::ExportOnly { aead } => {
+                write!(f, "the {aead} aead cannot seal or open messages")
+            }
+            Error::KeyScheduleInputTooLong { kdf } => {
+                write!(f, "key schedule input is too long for {kdf}")
+            }
+            Error::InvalidPsk { kdf } => {
+                write!(f, "invalid {kdf} pre-shared key or pre-shared key id")
+            }
+            Error::InvalidIkm { kem } => {
+                write!(f, "input keying material is too long for {kem}")
+            }
+            Error::KeypairMismatch => f.write_str("public key does not match private key"),
+            Error::KemMismatch { expected, found } => {
+                write!(f, "key is for {found}, not {expected}")
+            }
+            Error::InvalidKeyEncoding => f.write_str("invalid key encoding"),
+            Error::UnsupportedKeyEncoding { kem } => {
+                write!(f, "{kem} keys cannot be encoded in this format")
+            }
+            Error::InvalidContextState => f.write_str("invalid saved context state"),
+            Error::ContextConfigMismatch { expected, found } => {
+                write!(f, "context state was saved for {found}, not {expected}")
+            }
+            Error::MalformedStream => f.write_str("malformed hpke stream"),
+            Error::Hpke(e) => write!(f, "hpke error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Hpke(e) => Some(e),
+            Error::UnsupportedAlgorithm(e) => Some(e),
+            Error::PolicyViolation(e) => Some(e),
+            Error::InvalidPublicKey { .. }
+            | Error::InvalidPrivateKey { .. }
+            | Error::InvalidEncappedKey { .. }
+            | Error::DecryptionFailed { .. }
+            | Error::InvalidConfigString
+            | Error::InvalidSuiteId
+            | Error::UnsupportedMode { .. }
+            | Error::ExportOnly { .. }
+            | Error::KeyScheduleInputTooLong { .. }
+            | Error::InvalidPsk { .. }
+            | Error::InvalidIkm { .. }
+            | Error::KeypairMismatch
+            | Error::KemMismatch { .. }
+            | Error::InvalidKeyEncoding
+            | Error::UnsupportedKeyEncoding { .. }
+            | Error::InvalidContextState
+            | Error::ContextConfigMismatch { .. }
+            | Error::MalformedStream => None,
+        }
+    }
+}
+
+impl From<HpkeError> for Error {
+    fn from(e: HpkeError) -> Self {
+        Error::Hpke(e)
+    }
+}
+
+impl From<IdLookupError> for Error {
+    fn from(e: IdLookupError) -> Self {
+        Error::UnsupportedAlgorithm(e)
+    }
+}
+
+impl From<PolicyViolation> for Error {
+    fn from(e: PolicyViolation) -> Self {
+        Error::PolicyViolation(e)
+    }
+}
@@ -0,0 +1,80 @@
+use zeroize::Zeroizing;
+
+use crate::{setup_receiver, setup_sender, Config, Error};
+
+/// Encapsulated key and exported secret.
+///
+/// Obtained from [`sender_export`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncappedKeyAndSecret {
+    /// The encapsulated encryption key
+    pub encapped_key: Vec<u8>,
+
+    /// The exported secret, which is zeroized when dropped
+    pub secret: Zeroizing<Vec<u8>>,
+}
+
+impl EncappedKeyAndSecret {
+    /// returns (encapsulated key, exported secret)
+    #[must_use]
+    pub fn into_parts(self) -> (Vec<u8>, Zeroizing<Vec<u8>>) {
+        (self.encapped_key, self.secret)
+    }
+}
+
+/// Single-shot HPKE secret export for the sender.
+///
+/// `sender_export` implements `SendExport` from [RFC9180§6.2][1] without requiring compile time
+/// selection of an algorithm. Instead, the selected algorithm is provided through the [`Config`]
+/// passed as the first argument. The recipient derives the same secret with [`receiver_export`].
+///
+/// [1]: https://www.rfc-editor.org/rfc/rfc9180.html#section-6.2
+///
+/// # Errors
+///
+/// This will return a `Result::Err` variant if:
+///
+/// * we are unable to deserialize the recipient public key
+/// * there is an error in key encapsultion
+/// * `info` is too long for the selected [`Kdf`](crate::Kdf)
+/// * `len` is too large for the selected [`Kdf`](crate::Kdf)
+pub fn sender_export(
+    config: &Config,
+    recipient_public_key: &[u8],
+    info: &[u8],
+    exporter_context: &[u8],
+    len: usize,
+) -> Result<EncappedKeyAndSecret, Error> {
+    let (encapped_key, context) = setup_sender(config, recipient_public_key, info)?;
+    Ok(EncappedKeyAndSecret {
+        encapped_key,
+        secret: context.export(exporter_context, len)?,
+    })
+}
+
+/// Single-shot HPKE secret export for the recipient.
+///
+/// `receiver_export` implements `ReceiveExport` from [RFC9180§6.2][1] without requiring compile
+/// time selection of an algorithm. Instead, the selected algorithm is provided through the
+/// [`Config`] passed as the first argument.
+///
+/// [1]: https://www.rfc-editor.org/rfc/rfc9180.html#section-6.2
+///
+/// # Errors
+///
+/// This will return a `Result::Err` variant if:
+///
+/// * we are unable to deserialize the private key or encapsulated key
+/// * there is an error in key decapsulation
+/// * `info` is too long for the selected [`Kdf`](crate::Kdf)
+/// * `len` is too large for the selected [`Kdf`](crate::Kdf)
+pub fn receiver_export(
+    config: &Config,
+    private_key: &[u8],
+    encapped_key: &[u8],
+    info: &[u8],
+    exporter_context: &[u8],
+    len: usize,
+) -> Result<Zeroizing<Vec<u8>>, Error> {
+    setup_receiver(config, private_key, encapped_key, info)?.export(exporter_context, len)
+}
@@ -0,0 +1,149 @@
+/// The kind of algorithm an [`IdLookupError`] failed to find.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_crate::Serialize, serde_crate::Deserialize)
+)]
+#[cfg_attr(feature = "serde", serde(crate = "serde_crate"))]
+pub enum AlgorithmKind {
+    /// an [`Aead`](crate::Aead)
+    Aead,
+    /// a [`Kdf`](crate::Kdf)
+    Kdf,
+    /// a [`Kem`](crate::Kem)
+    Kem,
+}
+
+impl std::fmt::Display for AlgorithmKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            AlgorithmKind::Aead => "aead",
+            AlgorithmKind::Kdf => "kdf",
+            AlgorithmKind::Kem => "kem",
+        })
+    }
+}
+
+/// The id or name that an [`IdLookupError`] failed to find.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_crate::Serialize, serde_crate::Deserialize)
+)]
+#[cfg_attr(feature = "serde", serde(crate = "serde_crate"))]
+pub enum LookupValue {
+    /// an IANA HPKE registry id
+    Id(u16),
+    /// a name, as parsed by `FromStr`
+    Name(String),
+}
+
+impl std::fmt::Display for LookupValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LookupValue::Id(id) => write!(f, "id {id:#06x}"),
+            LookupValue::Name(name) => write!(f, "name {name:?}"),
+        }
+    }
+}
+
+/// An error from looking up an [`Aead`](crate::Aead), [`Kdf`](crate::Kdf) or [`Kem`](crate::Kem)
+/// by id or name.
+///
+/// An id that is registered with IANA but whose algorithm has been compiled out of this build of
+/// the crate is reported with the cargo feature that would enable it.
+///
+/// Since 0.9 this is no longer `Copy`, as it owns the name that failed to parse.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_crate::Serialize, serde_crate::Deserialize)
+)]
+#[cfg_attr(feature = "serde", serde(crate = "serde_crate"))]
+pub struct IdLookupError {
+    kind: AlgorithmKind,
+    value: LookupValue,
+    disabled_feature: Option<&'static str>,
+}
+
+impl IdLookupError {
+    pub(crate) fn id(kind: AlgorithmKind, id: u16) -> Self {
+        Self {
+            kind,
+            value: LookupValue::Id(id),
+            disabled_feature: required_features(kind, id)
+                .iter()
+                .find(|(_, enabled)| !enabled)
+                .map(|(feature, _)| *feature),
+        }
+    }
+
+    pub(crate) fn name(kind: AlgorithmKind, name: &str) -> Self {
+        Self {
+            kind,
+            value: LookupValue::Name(name.to_string()),
+            disabled_feature: None,
+        }
+    }
+
+    /// the kind of algorithm that was looked up
+    #[must_use]
+    pub fn kind(&self) -> AlgorithmKind {
+        self.kind
+    }
+
+    /// the id or name that was looked up
+    #[must_use]
+    pub fn value(&self) -> &LookupValue {
+        &self.value
+    }
+
+    /// If the id is registered with IANA for an algorithm that this crate supports, but that was
+    /// compiled out, the cargo feature that would enable it. This is `None` for an id that this
+    /// crate does not support at all.
+    #[must_use]
+    pub fn disabled_feature(&self) -> Option<&'static str> {
+        self.disabled_feature
+    }
+}
+
+impl std::fmt::Display for IdLookupError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Self {
+            kind,
+            value,
+            disabled_feature,
+        } = self;
+        match disabled_feature {
+            Some(feature) => write!(f, "{kind} {value} requires the {feature:?} feature"),
+            None => write!(f, "{kind} {value} not recognized"),
+        }
+    }
+}
+
+impl std::error::Error for IdLookupError {}
+
+/// The cargo features needed for each algorithm this crate supports, by IANA id, and whether
+/// each is enabled.
+fn required_features(kind: AlgorithmKind, id: u16) -> &'static [(&'static str, bool)] {
+    const AES: (&str, bool) = ("aes", cfg!(feature = "aes"));
+    const CHACHA: (&str, bool) = ("chacha", cfg!(feature = "chacha"));
+    const HKDFSHA2: (&str, bool) = ("hkdfsha2", cfg!(feature = "hkdfsha2"));
+    const SHAKE: (&str, bool) = ("shake", cfg!(feature = "shake"));
+    const NISTP: (&str, bool) = ("nistp", cfg!(feature = "nistp"));
+    const X25519: (&str, bool) = ("x25519", cfg!(feature = "x25519"));
+    const MLKEM: (&str, bool) = ("mlkem", cfg!(feature = "mlkem"));
+
+    match (kind, id) {
+        (AlgorithmKind::Aead, 0x0001 | 0x0002) => &[AES],
+        (AlgorithmKind::Aead, 0x0003) => &[CHACHA],
+        (AlgorithmKind::Kdf, 0x0001..=0x0003) => &[HKDFSHA2],
+        (AlgorithmKind::Kdf, 0x0010..=0x0013) => &[SHAKE],
+        (AlgorithmKind::Kem, 0x0010..=0x0012) => &[NISTP],
+        (AlgorithmKind::Kem, 0x0020) => &[X25519],
+        (AlgorithmKind::Kem, 0x0041 | 0x0042) => &[MLKEM],
+        (AlgorithmKind::Kem, 0x647a) => &[MLKEM, X25519],
+        (AlgorithmKind::Kem, 0x0050 | 0x0051) => &[MLKEM, NISTP],
+        _ => &[],
+    }
+}
@@ -0,0 +1,400 @@
+use serde_crate::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::{keypair::public_key_from_private, Config, Error, Kem, Keypair, PublicKey};
+
+/// A JSON Web Key, as described in [RFC7517](https://www.rfc-editor.org/rfc/rfc7517), holding
+/// an HPKE public key or keypair.
+///
+/// Elliptic curve [`Kem`]s use the `EC` and `OKP` key types from
+/// [RFC7518](https://www.rfc-editor.org/rfc/rfc7518#section-6.2) and
+/// [RFC8037](https://www.rfc-editor.org/rfc/rfc8037), and ML-KEM uses the `AKP` key type from
+/// [draft-ietf-jose-pqc-kem](https://datatracker.ietf.org/doc/draft-ietf-jose-pqc-kem/). The
+/// hybrid [`Kem`]s have no JWK representation.
+///
+/// A [`Jwk`] can be serialized with any serde format. A JWK Set is a JSON object whose `keys`
+/// member is an array of JWKs.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(crate = "serde_crate")]
+pub struct Jwk {
+    /// the key type: `EC`, `OKP` or `AKP`
+    pub kty: String,
+
+    /// the curve of an `EC` or `OKP` key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crv: Option<String>,
+
+    /// the algorithm of an `AKP` key, or for other key types, the JOSE HPKE algorithm
+    /// identifying the [`Config`] the key is intended for. See [`Jwk::config`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alg: Option<String>,
+
+    /// the key ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kid: Option<String>,
+
+    /// the x coordinate of an `EC` key, or the public key of an `OKP` key
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "base64url")]
+    pub x: Option<Vec<u8>>,
+
+    /// the y coordinate of an `EC` key
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "base64url")]
+    pub y: Option<Vec<u8>>,
+
+    /// the private key of an `EC` or `OKP` key
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "base64url")]
+    pub d: Option<Zeroizing<Vec<u8>>>,
+
+    /// the public key of an `AKP` key
+    #[serde(
+        rename = "pub",
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "base64url"
+    )]
+    pub public: Option<Vec<u8>>,
+
+    /// the private key of an `AKP` key. For ML-KEM, this is the 64 byte seed.
+    #[serde(
+        rename = "priv",
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "base64url"
+    )]
+    pub private: Option<Zeroizing<Vec<u8>>>,
+}
+
+/// How the public key of a [`Kem`] is represented in a [`Jwk`].
+#[derive(Clone, Copy)]
+enum KeyType {
+    /// an `EC` key on the named curve
+    #[cfg(feature = "nistp")]
+    Ec(&'static str),
+
+    /// an `OKP` key on the named curve
+    #[cfg(feature = "x25519")]
+    Okp(&'static str),
+
+    /// an `AKP` key for the named algorithm
+    #[cfg(feature = "mlkem")]
+    Akp(&'static str),
+}
+
+const KEY_TYPES: &[(Kem, KeyType)] = &[
+    #[cfg(feature = "nistp")]
+    (Kem::DhP256HkdfSha256, KeyType::Ec("P-256")),
+    #[cfg(feature = "nistp")]
+    (Kem::DhP384HkdfSha384, KeyType::Ec("P-384")),
+    #[cfg(feature = "nistp")]
+    (Kem::DhP521HkdfSha512, KeyType::Ec("P-521")),
+    #[cfg(feature = "x25519")]
+    (Kem::X25519HkdfSha256, KeyType::Okp("X25519")),
+    #[cfg(feature = "mlkem")]
+    (Kem::MlKem768, KeyType::Akp("ML-KEM-768")),
+    #[cfg(feature = "mlkem")]
+    (Kem::MlKem1024, KeyType::Akp("ML-KEM-1024")),
+];
+
+/// The JOSE HPKE algorithms, as described in
+/// [draft-ietf-jose-hpke-encrypt](https://datatracker.ietf.org/doc/draft-ietf-jose-hpke-encrypt/).
+const CONFIG_ALGS: &[(&str, Config)] = &[
+    #[cfg(all(feature = "nistp", feature = "hkdfsha2", feature = "aes"))]
+    (
+        "HPKE-0",
+        Config {
+            aead: crate::Aead::AesGcm128,
+            kdf: crate::Kdf::Sha256,
+            kem: Kem::DhP256HkdfSha256,
+        },
+    ),
+    #[cfg(all(feature = "nistp", feature = "hkdfsha2", feature = "aes"))]
+    (
+        "HPKE-1",
+        Config {
+            aead: crate::Aead::AesGcm256,
+            kdf: crate::Kdf::Sha384,
+            kem: Kem::DhP384HkdfSha384,
+        },
+    ),
+    #[cfg(all(feature = "nistp", feature = "hkdfsha2", feature = "aes"))]
+    (
+        "HPKE-2",
+        Config {
+            aead: crate::Aead::AesGcm256,
+            kdf: crate::Kdf::Sha512,
+            kem: Kem::DhP521HkdfSha512,
+        },
+    ),
+    #[cfg(all(feature = "x25519", feature = "hkdfsha2", feature = "aes"))]
+    (
+        "HPKE-3",
+        Config {
+            aead: crate::Aead::AesGcm128,
+            kdf: crate::Kdf::Sha256,
+            kem: Kem::X25519HkdfSha256,
+        },
+    ),
+    #[cfg(all(feature = "x25519", feature = "hkdfsha2", feature = "chacha"))]
+    (
+        "HPKE-4",
+        Config {
+            aead: crate::Aead::ChaCha20Poly1305,
+            kdf: crate::Kdf::Sha256,
+            kem: Kem::X25519HkdfSha256,
+        },
+    ),
+];
+
+impl KeyType {
+    fn for_kem(kem: Kem) -> Result<Self, Error> {
+        KEY_TYPES
+            .iter()
+            .find(|(candidate, _)| *candidate == kem)
+            .map(|(_, key_type)| *key_type)
+            .ok_or(Error::UnsupportedKeyEncoding { kem })
+    }
+}
+
+impl Jwk {
+    /// The [`Config`] identified by the `alg` member of this [`Jwk`], if it is one of the JOSE
+    /// HPKE algorithms described in
+    /// [draft-ietf-jose-hpke-encrypt](https://datatracker.ietf.org/doc/draft-ietf-jose-hpke-encrypt/).
+    /// `AKP` keys use `alg` to identify their own algorithm, so they never carry a [`Config`].
+    #[must_use]
+    pub fn config(&self) -> Option<Config> {
+        if self.kty == "AKP" {
+            return None;
+        }
+        let alg = self.alg.as_deref()?;
+        CONFIG_ALGS
+            .iter()
+            .find(|(candidate, _)| *candidate == alg)
+            .map(|(_, config)| *config)
+    }
+
+    fn new(config: &Config, public_key: &[u8]) -> Result<Self, Error> {
+        let mut jwk = Self {
+            kty: String::new(),
+            crv: None,
+            alg: CONFIG_ALGS
+                .iter()
+                .find(|(_, candidate)| candidate == config)
+                .map(|(alg, _)| (*alg).to_string()),
+            kid: None,
+            x: None,
+            y: None,
+            d: None,
+            public: None,
+            private: None,
+        };
+        match KeyType::for_kem(config.kem)? {
+            #[cfg(feature = "nistp")]
+            KeyType::Ec(crv) => {
+                // an uncompressed point: 0x04 || x || y
+                let (x, y) = public_key[1..].split_at((public_key.len() - 1) / 2);
+                jwk.kty = "EC".into();
+                jwk.crv = Some(crv.into());
+                jwk.x = Some(x.to_vec());
+                jwk.y = Some(y.to_vec());
+            }
+
+            #[cfg(feature = "x25519")]
+            KeyType::Okp(crv) => {
+                jwk.kty = "OKP".into();
+                jwk.crv = Some(crv.into());
+                jwk.x = Some(public_key.to_vec());
+            }
+
+            #[cfg(feature = "mlkem")]
+            KeyType::Akp(alg) => {
+                jwk.kty = "AKP".into();
+                jwk.alg = Some(alg.into());
+                jwk.public = Some(public_key.to_vec());
+            }
+        }
+        Ok(jwk)
+    }
+
+    fn key_type(&self) -> Result<(Kem, KeyType), Error> {
+        let (kem, key_type) = KEY_TYPES
+            .iter()
+            .find(|(_, key_type)| match key_type {
+                #[cfg(feature = "nistp")]
+                KeyType::Ec(crv) => self.kty == "EC" && self.crv.as_deref() == Some(crv),
+                #[cfg(feature = "x25519")]
+                KeyType::Okp(crv) => self.kty == "OKP" && self.crv.as_deref() == Some(crv),
+                #[cfg(feature = "mlkem")]
+                KeyType::Akp(alg) => self.kty == "AKP" && self.alg.as_deref() == Some(alg),
+            })
+            .ok_or(Error::InvalidKeyEncoding)?;
+        if self.config().is_some_and(|config| config.kem != *kem) {
+            return Err(Error::InvalidKeyEncoding);
+        }
+        Ok((*kem, *key_type))
+    }
+
+    fn public_key(&self, key_type: KeyType) -> Result<Vec<u8>, Error> {
+        let public_key = match key_type {
+            #[cfg(feature = "nistp")]
+            KeyType::Ec(_) => match (&self.x, &self.y) {
+                (Some(x), Some(y)) if x.len() == y.len() => {
+                    Some([&[0x04], &x[..], &y[..]].concat())
+                }
+                _ => None,
+            },
+
+            #[cfg(feature = "x25519")]
+            KeyType::Okp(_) => self.x.clone(),
+
+            #[cfg(feature = "mlkem")]
+            KeyType::Akp(_) => self.public.clone(),
+        };
+        public_key.ok_or(Error::InvalidKeyEncoding)
+    }
+
+    fn private_key(&self, key_type: KeyType) -> Option<&[u8]> {
+        let private_key = match key_type {
+            #[cfg(feature = "nistp")]
+            KeyType::Ec(_) => &self.d,
+
+            #[cfg(feature = "x25519")]
+            KeyType::Okp(_) => &self.d,
+
+            #[cfg(feature = "mlkem")]
+            KeyType::Akp(_) => &self.private,
+        };
+        private_key.as_deref().map(Vec::as_slice)
+    }
+}
+
+impl std::fmt::Debug for Jwk {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Jwk")
+            .field("kty", &self.kty)
+            .field("crv", &self.crv)
+            .field("alg", &self.alg)
+            .field("kid", &self.kid)
+            .field("x", &self.x)
+            .field("y", &self.y)
+            .field("public", &self.public)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Keypair {
+    /// Represent this [`Keypair`] as a private [`Jwk`] for `config.kem`. If `config` is one of
+    /// the JOSE HPKE algorithms, it is included as the `alg` member.
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if:
+    ///
+    /// * the selected [`Kem`] has no JWK representation
+    /// * the keypair is not a valid keypair for the selected [`Kem`]
+    pub fn to_jwk(&self, config: &Config) -> Result<Jwk, Error> {
+        self.validate(config.kem)?;
+        let mut jwk = Jwk::new(config, &self.public_key)?;
+        let private_key = Some(self.private_key.clone());
+        if jwk.kty == "AKP" {
+            jwk.private = private_key;
+        } else {
+            jwk.d = private_key;
+        }
+        Ok(jwk)
+    }
+
+    /// Read a private [`Jwk`], returning the [`Kem`] identified by its key type and the
+    /// [`Keypair`].
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if:
+    ///
+    /// * the JWK is missing members, or does not identify a supported [`Kem`]
+    /// * the JWK has an `alg` member for a [`Config`] with a different [`Kem`]
+    /// * we are unable to deserialize the private key
+    /// * the public key does not match the private key
+    pub fn from_jwk(jwk: &Jwk) -> Result<(Kem, Self), Error> {
+        let (kem, key_type) = jwk.key_type()?;
+        let private_key = jwk.private_key(key_type).ok_or(Error::InvalidKeyEncoding)?;
+        let public_key = public_key_from_private(kem, private_key)?;
+        if jwk.public_key(key_type)? != public_key {
+            return Err(Error::KeypairMismatch);
+        }
+        Ok((
+            kem,
+            Self {
+                private_key: Zeroizing::new(private_key.to_vec()),
+                public_key,
+            },
+        ))
+    }
+}
+
+impl PublicKey {
+    /// Represent this [`PublicKey`] as a public [`Jwk`]. If `config` is one of the JOSE HPKE
+    /// algorithms, it is included as the `alg` member.
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if:
+    ///
+    /// * `config` is for a different [`Kem`] than this key
+    /// * the [`Kem`] of this key has no JWK representation
+    pub fn to_jwk(&self, config: &Config) -> Result<Jwk, Error> {
+        if config.kem != self.kem() {
+            return Err(Error::KemMismatch {
+                expected: config.kem,
+                found: self.kem(),
+            });
+        }
+        Jwk::new(config, self.as_bytes())
+    }
+
+    /// Read the public key from a public or private [`Jwk`], taking its [`Kem`] from the key
+    /// type.
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if:
+    ///
+    /// * the JWK is missing members, or does not identify a supported [`Kem`]
+    /// * the JWK has an `alg` member for a [`Config`] with a different [`Kem`]
+    /// * we are unable to deserialize the public key
+    pub fn from_jwk(jwk: &Jwk) -> Result<Self, Error> {
+        let (kem, key_type) = jwk.key_type()?;
+        Self::new(kem, &jwk.public_key(key_type)?)
+    }
+}
+
+/// serde helpers for optional base64url encoded members
+mod base64url {
+    use base64ct::{Base64UrlUnpadded, Encoding};
+    use serde_crate::{de::Error, Deserialize, Deserializer, Serializer};
+    use zeroize::{Zeroize, Zeroizing};
+
+    pub(super) fn serialize<S: Serializer, T: AsRef<[u8]>>(
+        value: &Option<T>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(bytes) => serializer.serialize_str(&Zeroizing::new(
+                Base64UrlUnpadded::encode_string(bytes.as_ref()),
+            )),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>, T: From<Vec<u8>>>(
+        deserializer: D,
+    ) -> Result<Option<T>, D::Error> {
+        let Some(mut encoded) = Option::<String>::deserialize(deserializer)? else {
+            return Ok(None);
+        };
+        let decoded = Base64UrlUnpadded::decode_vec(&encoded);
+        encoded.zeroize();
+        decoded
+            .map(|bytes| Some(T::from(bytes)))
+            .map_err(D::Error::custom)
+    }
+}
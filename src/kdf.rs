@@ -1,8 +1,9 @@
 use std::str::FromStr;
 
+use hpke::hybrid_array::typenum::Unsigned;
 use num_enum::TryFromPrimitive;
 
-use crate::IdLookupError;
+use crate::{AlgorithmKind, Error, IdLookupError, QuantumResistance};
 
 /// A key derivation function used in HPKE.
 ///
@@ -61,11 +62,139 @@ impl FromStr for Kdf {
             "turboshake128" => Ok(Self::TurboShake128),
             #[cfg(feature = "shake")]
             "turboshake256" => Ok(Self::TurboShake256),
-            _ => Err(IdLookupError("kdf not recognized")),
+            _ => Err(IdLookupError::name(AlgorithmKind::Kdf, s)),
         }
     }
 }
 
+/// The id of the [`Kdf`] in the [IANA HPKE registry][1], which converts back with `TryFrom<u16>`.
+///
+/// [1]: https://www.iana.org/assignments/hpke/hpke.xhtml
+impl From<Kdf> for u16 {
+    fn from(kdf: Kdf) -> u16 {
+        kdf as u16
+    }
+}
+
+/// Writes the name of the [`Kdf`] in the [IANA HPKE registry][1], such as `HKDF-SHA256`, which
+/// [`FromStr`] parses back.
+///
+/// [1]: https://www.iana.org/assignments/hpke/hpke.xhtml
+impl std::fmt::Display for Kdf {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            #[cfg(feature = "hkdfsha2")]
+            Kdf::Sha256 => "HKDF-SHA256",
+            #[cfg(feature = "hkdfsha2")]
+            Kdf::Sha384 => "HKDF-SHA384",
+            #[cfg(feature = "hkdfsha2")]
+            Kdf::Sha512 => "HKDF-SHA512",
+            #[cfg(feature = "shake")]
+            Kdf::Shake128 => "SHAKE128",
+            #[cfg(feature = "shake")]
+            Kdf::Shake256 => "SHAKE256",
+            #[cfg(feature = "shake")]
+            Kdf::TurboShake128 => "TurboSHAKE128",
+            #[cfg(feature = "shake")]
+            Kdf::TurboShake256 => "TurboSHAKE256",
+        })
+    }
+}
+
+impl Kdf {
+    /// The nominal security level of this [`Kdf`] in bits: 128, 192 or 256.
+    #[must_use]
+    pub const fn security_level(self) -> u16 {
+        match self {
+            #[cfg(feature = "hkdfsha2")]
+            Kdf::Sha256 => 128,
+            #[cfg(feature = "hkdfsha2")]
+            Kdf::Sha384 => 192,
+            #[cfg(feature = "hkdfsha2")]
+            Kdf::Sha512 => 256,
+            #[cfg(feature = "shake")]
+            Kdf::Shake128 | Kdf::TurboShake128 => 128,
+            #[cfg(feature = "shake")]
+            Kdf::Shake256 | Kdf::TurboShake256 => 256,
+        }
+    }
+
+    /// Whether this [`Kdf`] resists attack by a quantum computer. Every supported [`Kdf`] is
+    /// [`QuantumResistance::PostQuantum`] at its [`security_level`](Kdf::security_level), which
+    /// is how the NIST post-quantum security categories are defined.
+    #[must_use]
+    pub const fn quantum_resistance(self) -> QuantumResistance {
+        QuantumResistance::PostQuantum
+    }
+
+    /// Whether this [`Kdf`] is approved for use in FIPS 140-3 validated modules. HKDF is
+    /// approved per [SP 800-56C][1], while the single-stage SHAKE and TurboSHAKE KDFs of
+    /// [draft-ietf-hpke-pq][2] are not. This describes the algorithm, not the validation status
+    /// of the implementation in use.
+    ///
+    /// [1]: https://doi.org/10.6028/NIST.SP.800-56Cr2
+    /// [2]: https://datatracker.ietf.org/doc/draft-ietf-hpke-pq/
+    #[must_use]
+    pub const fn is_fips_approved(self) -> bool {
+        match self {
+            #[cfg(feature = "hkdfsha2")]
+            Kdf::Sha256 | Kdf::Sha384 | Kdf::Sha512 => true,
+            #[cfg(feature = "shake")]
+            Kdf::Shake128 | Kdf::Shake256 | Kdf::TurboShake128 | Kdf::TurboShake256 => false,
+        }
+    }
+
+    /// The security strength of this [`Kdf`] in bytes, `Nh` in [RFC9180§7.2][1] and
+    /// [draft-ietf-hpke-pq][2].
+    ///
+    /// [1]: https://www.rfc-editor.org/rfc/rfc9180.html#section-7.2
+    /// [2]: https://datatracker.ietf.org/doc/draft-ietf-hpke-pq/
+    #[must_use]
+    pub const fn n_h(self) -> usize {
+        match self {
+            #[cfg(feature = "hkdfsha2")]
+            Kdf::Sha256 => n_h::<hpke::kdf::HkdfSha256>(),
+            #[cfg(feature = "hkdfsha2")]
+            Kdf::Sha384 => n_h::<hpke::kdf::HkdfSha384>(),
+            #[cfg(feature = "hkdfsha2")]
+            Kdf::Sha512 => n_h::<hpke::kdf::HkdfSha512>(),
+            #[cfg(feature = "shake")]
+            Kdf::Shake128 => n_h::<hpke::kdf::KdfShake128>(),
+            #[cfg(feature = "shake")]
+            Kdf::Shake256 => n_h::<hpke::kdf::KdfShake256>(),
+            #[cfg(feature = "shake")]
+            Kdf::TurboShake128 => n_h::<hpke::kdf::KdfTurboShake128>(),
+            #[cfg(feature = "shake")]
+            Kdf::TurboShake256 => n_h::<hpke::kdf::KdfTurboShake256>(),
+        }
+    }
+
+    /// checks that the key schedule `inputs` of this [`Kdf`], which are the info string,
+    /// pre-shared key and pre-shared key id, can be length-prefixed: the one-stage SHAKE and
+    /// TurboSHAKE KDFs encode their lengths in two bytes
+    pub(crate) fn check_key_schedule_input(self, inputs: &[&[u8]]) -> Result<(), Error> {
+        let two_byte_lengths = match self {
+            #[cfg(feature = "hkdfsha2")]
+            Kdf::Sha256 | Kdf::Sha384 | Kdf::Sha512 => false,
+            #[cfg(feature = "shake")]
+            Kdf::Shake128 | Kdf::Shake256 | Kdf::TurboShake128 | Kdf::TurboShake256 => true,
+        };
+        if two_byte_lengths
+            && inputs
+                .iter()
+                .any(|input| input.len() > usize::from(u16::MAX))
+        {
+            Err(Error::KeyScheduleInputTooLong { kdf: self })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+const fn n_h<KdfT: hpke::kdf::Kdf>() -> usize {
+    KdfT::Nh::USIZE
+}
+
 /// An iterable slice of [`Kdf`] variants
 pub const KDF_ALL: &[Kdf] = &[
     #[cfg(feature = "hkdfsha2")]
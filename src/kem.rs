@@ -1,8 +1,9 @@
 use std::str::FromStr;
 
+use hpke::{hybrid_array::typenum::Unsigned, Serializable};
 use num_enum::TryFromPrimitive;
 
-use crate::{IdLookupError, Keypair};
+use crate::{AlgorithmKind, Error, IdLookupError, Keypair, Mode, QuantumResistance};
 
 /// An asymmetric key encapsulation mechanism.
 ///
@@ -83,22 +84,267 @@ impl FromStr for Kem {
             #[cfg(feature = "mlkem")]
             "mlkem1024" => Ok(Self::MlKem1024),
             #[cfg(all(feature = "mlkem", feature = "x25519"))]
-            "xwing" | "mlkem768x25119" | "mlkem768-x25119" => Ok(Self::XWing),
+            "xwing" | "mlkem768x25519" | "mlkem768x25119" => Ok(Self::XWing),
             #[cfg(all(feature = "mlkem", feature = "nistp"))]
-            "mlkem768p256" | "mlkem768-p256" => Ok(Self::MlKem768P256),
+            "mlkem768p256" => Ok(Self::MlKem768P256),
             #[cfg(all(feature = "mlkem", feature = "nistp"))]
-            "mlkem1024p384" | "mlkem1024-p384" => Ok(Self::MlKem1024P384),
-            _ => Err(IdLookupError("kem not recognized")),
+            "mlkem1024p384" => Ok(Self::MlKem1024P384),
+            _ => Err(IdLookupError::name(AlgorithmKind::Kem, s)),
         }
     }
 }
 
+/// The id of the [`Kem`] in the [IANA HPKE registry][1], which converts back with `TryFrom<u16>`.
+///
+/// [1]: https://www.iana.org/assignments/hpke/hpke.xhtml
+impl From<Kem> for u16 {
+    fn from(kem: Kem) -> u16 {
+        kem as u16
+    }
+}
+
+/// Writes the name of the [`Kem`] in the [IANA HPKE registry][1] and [draft-ietf-hpke-pq][2],
+/// such as `DHKEM(X25519, HKDF-SHA256)`, which [`FromStr`] parses back.
+///
+/// [1]: https://www.iana.org/assignments/hpke/hpke.xhtml
+/// [2]: https://www.ietf.org/archive/id/draft-ietf-hpke-pq-05.html#table-1
+impl std::fmt::Display for Kem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            #[cfg(feature = "nistp")]
+            Kem::DhP256HkdfSha256 => "DHKEM(P-256, HKDF-SHA256)",
+            #[cfg(feature = "nistp")]
+            Kem::DhP384HkdfSha384 => "DHKEM(P-384, HKDF-SHA384)",
+            #[cfg(feature = "nistp")]
+            Kem::DhP521HkdfSha512 => "DHKEM(P-521, HKDF-SHA512)",
+            #[cfg(feature = "x25519")]
+            Kem::X25519HkdfSha256 => "DHKEM(X25519, HKDF-SHA256)",
+            #[cfg(feature = "mlkem")]
+            Kem::MlKem768 => "ML-KEM-768",
+            #[cfg(feature = "mlkem")]
+            Kem::MlKem1024 => "ML-KEM-1024",
+            #[cfg(all(feature = "mlkem", feature = "x25519"))]
+            Kem::XWing => "MLKEM768-X25519",
+            #[cfg(all(feature = "mlkem", feature = "nistp"))]
+            Kem::MlKem768P256 => "MLKEM768-P256",
+            #[cfg(all(feature = "mlkem", feature = "nistp"))]
+            Kem::MlKem1024P384 => "MLKEM1024-P384",
+        })
+    }
+}
+
 impl Kem {
     /// generate a [`Keypair`] for this [`Kem`].
     #[must_use]
     pub fn gen_keypair(self) -> Keypair {
         crate::gen_keypair(self)
     }
+
+    /// deterministically derive a [`Keypair`] for this [`Kem`] from input keying material. See
+    /// [`derive_keypair`](crate::derive_keypair).
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if `ikm` is longer than 65535 bytes and this
+    /// [`Kem`] is post-quantum or hybrid.
+    pub fn derive_keypair(self, ikm: &[u8]) -> Result<Keypair, Error> {
+        crate::derive_keypair(self, ikm)
+    }
+
+    /// Compute the encoded public key corresponding to an encoded private key for this [`Kem`].
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if we are unable to deserialize the private key.
+    pub fn public_key_from_private(self, private_key: &[u8]) -> Result<Vec<u8>, Error> {
+        crate::keypair::public_key_from_private(self, private_key)
+    }
+
+    /// whether this [`Kem`] provides `AuthEncap` and `AuthDecap`, and can therefore be used with
+    /// the Auth and AuthPSK [`Mode`]s.
+    ///
+    /// The Diffie-Hellman based KEMs support authentication, while ML-KEM and the hybrid KEMs
+    /// built on it do not.
+    #[must_use]
+    pub const fn supports_auth(self) -> bool {
+        match self {
+            #[cfg(feature = "nistp")]
+            Kem::DhP256HkdfSha256 | Kem::DhP384HkdfSha384 | Kem::DhP521HkdfSha512 => true,
+            #[cfg(feature = "x25519")]
+            Kem::X25519HkdfSha256 => true,
+            #[cfg(feature = "mlkem")]
+            Kem::MlKem768 | Kem::MlKem1024 => false,
+            #[cfg(all(feature = "mlkem", feature = "x25519"))]
+            Kem::XWing => false,
+            #[cfg(all(feature = "mlkem", feature = "nistp"))]
+            Kem::MlKem768P256 | Kem::MlKem1024P384 => false,
+        }
+    }
+
+    /// The nominal security level of this [`Kem`] in bits: 128, 192 or 256.
+    ///
+    /// For the Diffie-Hellman based KEMs this is their strength against a classical attacker,
+    /// and they provide none against a quantum one. ML-KEM-768 and ML-KEM-1024 meet NIST
+    /// post-quantum security categories 3 and 5, and the hybrid KEMs are rated by their ML-KEM
+    /// component. See [`Kem::quantum_resistance`].
+    #[must_use]
+    pub const fn security_level(self) -> u16 {
+        match self {
+            #[cfg(feature = "nistp")]
+            Kem::DhP256HkdfSha256 => 128,
+            #[cfg(feature = "nistp")]
+            Kem::DhP384HkdfSha384 => 192,
+            #[cfg(feature = "nistp")]
+            Kem::DhP521HkdfSha512 => 256,
+            #[cfg(feature = "x25519")]
+            Kem::X25519HkdfSha256 => 128,
+            #[cfg(feature = "mlkem")]
+            Kem::MlKem768 => 192,
+            #[cfg(feature = "mlkem")]
+            Kem::MlKem1024 => 256,
+            #[cfg(all(feature = "mlkem", feature = "x25519"))]
+            Kem::XWing => 192,
+            #[cfg(all(feature = "mlkem", feature = "nistp"))]
+            Kem::MlKem768P256 => 192,
+            #[cfg(all(feature = "mlkem", feature = "nistp"))]
+            Kem::MlKem1024P384 => 256,
+        }
+    }
+
+    /// whether this [`Kem`] resists attack by a quantum computer
+    #[must_use]
+    pub const fn quantum_resistance(self) -> QuantumResistance {
+        match self {
+            #[cfg(feature = "nistp")]
+            Kem::DhP256HkdfSha256 | Kem::DhP384HkdfSha384 | Kem::DhP521HkdfSha512 => {
+                QuantumResistance::Classical
+            }
+            #[cfg(feature = "x25519")]
+            Kem::X25519HkdfSha256 => QuantumResistance::Classical,
+            #[cfg(feature = "mlkem")]
+            Kem::MlKem768 | Kem::MlKem1024 => QuantumResistance::PostQuantum,
+            #[cfg(all(feature = "mlkem", feature = "x25519"))]
+            Kem::XWing => QuantumResistance::Hybrid,
+            #[cfg(all(feature = "mlkem", feature = "nistp"))]
+            Kem::MlKem768P256 | Kem::MlKem1024P384 => QuantumResistance::Hybrid,
+        }
+    }
+
+    /// Whether this [`Kem`] is approved for use in FIPS 140-3 validated modules: ECDH over the
+    /// NIST curves per [SP 800-56A][1], ML-KEM per [FIPS 203][2], and their combination per
+    /// [SP 800-227][3]. X25519, and X-Wing which is built on it, are not approved. This describes
+    /// the algorithm, not the validation status of the implementation in use.
+    ///
+    /// [1]: https://doi.org/10.6028/NIST.SP.800-56Ar3
+    /// [2]: https://csrc.nist.gov/pubs/fips/203/final
+    /// [3]: https://doi.org/10.6028/NIST.SP.800-227
+    #[must_use]
+    pub const fn is_fips_approved(self) -> bool {
+        match self {
+            #[cfg(feature = "nistp")]
+            Kem::DhP256HkdfSha256 | Kem::DhP384HkdfSha384 | Kem::DhP521HkdfSha512 => true,
+            #[cfg(feature = "x25519")]
+            Kem::X25519HkdfSha256 => false,
+            #[cfg(feature = "mlkem")]
+            Kem::MlKem768 | Kem::MlKem1024 => true,
+            #[cfg(all(feature = "mlkem", feature = "x25519"))]
+            Kem::XWing => false,
+            #[cfg(all(feature = "mlkem", feature = "nistp"))]
+            Kem::MlKem768P256 | Kem::MlKem1024P384 => true,
+        }
+    }
+
+    /// The length in bytes of an encapsulated key for this [`Kem`], `Nenc` in
+    /// [RFC9180§7.1][1] and [draft-ietf-hpke-pq][2]. This is also the length of the encapsulated
+    /// key that precedes the ciphertext when they are concatenated.
+    ///
+    /// [1]: https://www.rfc-editor.org/rfc/rfc9180.html#section-7.1
+    /// [2]: https://datatracker.ietf.org/doc/draft-ietf-hpke-pq/
+    #[must_use]
+    pub const fn n_enc(self) -> usize {
+        self.sizes().enc
+    }
+
+    /// The length in bytes of an encoded public key for this [`Kem`], `Npk` in
+    /// [RFC9180§7.1][1] and [draft-ietf-hpke-pq][2].
+    ///
+    /// [1]: https://www.rfc-editor.org/rfc/rfc9180.html#section-7.1
+    /// [2]: https://datatracker.ietf.org/doc/draft-ietf-hpke-pq/
+    #[must_use]
+    pub const fn n_pk(self) -> usize {
+        self.sizes().pk
+    }
+
+    /// The length in bytes of an encoded private key for this [`Kem`], `Nsk` in
+    /// [RFC9180§7.1][1] and [draft-ietf-hpke-pq][2].
+    ///
+    /// [1]: https://www.rfc-editor.org/rfc/rfc9180.html#section-7.1
+    /// [2]: https://datatracker.ietf.org/doc/draft-ietf-hpke-pq/
+    #[must_use]
+    pub const fn n_sk(self) -> usize {
+        self.sizes().sk
+    }
+
+    /// The length in bytes of the shared secret produced by this [`Kem`], `Nsecret` in
+    /// [RFC9180§7.1][1] and [draft-ietf-hpke-pq][2].
+    ///
+    /// [1]: https://www.rfc-editor.org/rfc/rfc9180.html#section-7.1
+    /// [2]: https://datatracker.ietf.org/doc/draft-ietf-hpke-pq/
+    #[must_use]
+    pub const fn n_secret(self) -> usize {
+        self.sizes().secret
+    }
+
+    const fn sizes(self) -> Sizes {
+        match self {
+            #[cfg(feature = "nistp")]
+            Kem::DhP256HkdfSha256 => Sizes::of::<hpke::kem::DhP256HkdfSha256>(),
+            #[cfg(feature = "nistp")]
+            Kem::DhP384HkdfSha384 => Sizes::of::<hpke::kem::DhP384HkdfSha384>(),
+            #[cfg(feature = "nistp")]
+            Kem::DhP521HkdfSha512 => Sizes::of::<hpke::kem::DhP521HkdfSha512>(),
+            #[cfg(feature = "x25519")]
+            Kem::X25519HkdfSha256 => Sizes::of::<hpke::kem::X25519HkdfSha256>(),
+            #[cfg(feature = "mlkem")]
+            Kem::MlKem768 => Sizes::of::<hpke::kem::MlKem768>(),
+            #[cfg(feature = "mlkem")]
+            Kem::MlKem1024 => Sizes::of::<hpke::kem::MlKem1024>(),
+            #[cfg(all(feature = "mlkem", feature = "x25519"))]
+            Kem::XWing => Sizes::of::<hpke::kem::XWing>(),
+            #[cfg(all(feature = "mlkem", feature = "nistp"))]
+            Kem::MlKem768P256 => Sizes::of::<hpke::kem::MlKem768P256>(),
+            #[cfg(all(feature = "mlkem", feature = "nistp"))]
+            Kem::MlKem1024P384 => Sizes::of::<hpke::kem::MlKem1024P384>(),
+        }
+    }
+
+    /// checks that this [`Kem`] can be used with the provided [`Mode`]
+    pub(crate) fn check_mode(self, mode: Mode) -> Result<(), Error> {
+        if mode.is_auth() && !self.supports_auth() {
+            Err(Error::UnsupportedMode { kem: self, mode })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// The lengths in bytes of the keys and shared secret of a [`Kem`].
+struct Sizes {
+    enc: usize,
+    pk: usize,
+    sk: usize,
+    secret: usize,
+}
+
+impl Sizes {
+    const fn of<KemT: hpke::kem::Kem>() -> Self {
+        Self {
+            enc: <KemT::EncappedKey as Serializable>::OutputSize::USIZE,
+            pk: <KemT::PublicKey as Serializable>::OutputSize::USIZE,
+            sk: <KemT::PrivateKey as Serializable>::OutputSize::USIZE,
+            secret: KemT::NSecret::USIZE,
+        }
+    }
 }
 
 /// An iterable slice of [`Kem`] variants
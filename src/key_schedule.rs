@@ -0,0 +1,204 @@
+//! The HPKE key schedule, as per [RFC9180§5.1][1] and [draft-ietf-hpke-pq][2].
+//!
+//! The [`hpke`] crate does not expose the key, base nonce and exporter secret of its contexts, so
+//! we derive them ourselves in order to support opening at arbitrary sequence numbers and saving
+//! contexts. Contexts seal, open and export with [`hpke`]'s own key schedule wherever it can be
+//! used: see `ContextState` for the exceptions.
+//!
+//! [1]: https://www.rfc-editor.org/rfc/rfc9180.html#section-5.1
+//! [2]: https://www.ietf.org/archive/id/draft-ietf-hpke-pq-05.html
+
+use zeroize::Zeroizing;
+
+use crate::{Config, Error, Kdf, Mode};
+
+const VERSION_LABEL: &[u8] = b"HPKE-v1";
+
+/// The secrets derived by the key schedule for a [`Config`].
+pub(crate) struct KeySchedule {
+    pub(crate) key: Zeroizing<Vec<u8>>,
+    pub(crate) base_nonce: Zeroizing<Vec<u8>>,
+    pub(crate) exporter_secret: Zeroizing<Vec<u8>>,
+}
+
+/// The lengths in bytes of the [`KeySchedule`] secrets, `Nk`, `Nn` and `Nh`.
+#[derive(Clone, Copy)]
+pub(crate) struct Lengths {
+    pub(crate) key: usize,
+    pub(crate) base_nonce: usize,
+    pub(crate) exporter_secret: usize,
+}
+
+/// The input to the key schedule for a [`Mode`].
+pub(crate) struct ModeInput<'a> {
+    pub(crate) mode: Mode,
+    pub(crate) psk: &'a [u8],
+    pub(crate) psk_id: &'a [u8],
+}
+
+impl ModeInput<'_> {
+    pub(crate) const BASE: ModeInput<'static> = ModeInput {
+        mode: Mode::Base,
+        psk: &[],
+        psk_id: &[],
+    };
+}
+
+/// Runs the key schedule.
+///
+/// # Errors
+///
+/// This will return [`Error::KeyScheduleInputTooLong`] if `info`, the pre-shared key or the
+/// pre-shared key id is too long for the selected [`Kdf`].
+pub(crate) fn key_schedule(
+    config: &Config,
+    mode: &ModeInput<'_>,
+    shared_secret: &[u8],
+    info: &[u8],
+    lengths: Lengths,
+) -> Result<KeySchedule, Error> {
+    config
+        .kdf
+        .check_key_schedule_input(&[info, mode.psk, mode.psk_id])?;
+    let suite_id = config.suite_id();
+    Ok(match config.kdf {
+        #[cfg(feature = "hkdfsha2")]
+        Kdf::Sha256 => two_stage::<sha2::Sha256>(&suite_id, mode, shared_secret, info, lengths),
+        #[cfg(feature = "hkdfsha2")]
+        Kdf::Sha384 => two_stage::<sha2::Sha384>(&suite_id, mode, shared_secret, info, lengths),
+        #[cfg(feature = "hkdfsha2")]
+        Kdf::Sha512 => two_stage::<sha2::Sha512>(&suite_id, mode, shared_secret, info, lengths),
+        #[cfg(feature = "shake")]
+        Kdf::Shake128 => {
+            one_stage::<shake::Shake128>(&suite_id, mode, shared_secret, info, lengths)
+        }
+        #[cfg(feature = "shake")]
+        Kdf::Shake256 => {
+            one_stage::<shake::Shake256>(&suite_id, mode, shared_secret, info, lengths)
+        }
+        #[cfg(feature = "shake")]
+        Kdf::TurboShake128 => {
+            one_stage::<turboshake::TurboShake128>(&suite_id, mode, shared_secret, info, lengths)
+        }
+        #[cfg(feature = "shake")]
+        Kdf::TurboShake256 => {
+            one_stage::<turboshake::TurboShake256>(&suite_id, mode, shared_secret, info, lengths)
+        }
+    })
+}
+
+#[cfg(feature = "hkdfsha2")]
+fn two_stage<H>(
+    suite_id: &[u8],
+    mode: &ModeInput<'_>,
+    shared_secret: &[u8],
+    info: &[u8],
+    lengths: Lengths,
+) -> KeySchedule
+where
+    H: Clone + sha2::Digest + hkdf::hmac::EagerHash,
+{
+    let (psk_id_hash, _) = labeled_extract::<H>(&[], suite_id, b"psk_id_hash", mode.psk_id);
+    let (info_hash, _) = labeled_extract::<H>(&[], suite_id, b"info_hash", info);
+    let context = [&[mode.mode as u8][..], &psk_id_hash, &info_hash].concat();
+
+    let (_, secret) = labeled_extract::<H>(shared_secret, suite_id, b"secret", mode.psk);
+    let expand = |label: &[u8], len: usize| labeled_expand(&secret, suite_id, label, &context, len);
+
+    KeySchedule {
+        key: expand(b"key", lengths.key),
+        base_nonce: expand(b"base_nonce", lengths.base_nonce),
+        exporter_secret: expand(b"exp", lengths.exporter_secret),
+    }
+}
+
+#[cfg(feature = "hkdfsha2")]
+fn labeled_extract<H>(
+    salt: &[u8],
+    suite_id: &[u8],
+    label: &[u8],
+    ikm: &[u8],
+) -> (Zeroizing<Vec<u8>>, hkdf::Hkdf<H>)
+where
+    H: Clone + sha2::Digest + hkdf::hmac::EagerHash,
+{
+    let mut extract = hkdf::HkdfExtract::<H>::new(Some(salt));
+    extract.input_ikm(VERSION_LABEL);
+    extract.input_ikm(suite_id);
+    extract.input_ikm(label);
+    extract.input_ikm(ikm);
+    let (prk, hkdf) = extract.finalize();
+    (Zeroizing::new(prk.to_vec()), hkdf)
+}
+
+#[cfg(feature = "hkdfsha2")]
+fn labeled_expand<H>(
+    prk: &hkdf::Hkdf<H>,
+    suite_id: &[u8],
+    label: &[u8],
+    context: &[u8],
+    len: usize,
+) -> Zeroizing<Vec<u8>>
+where
+    H: Clone + sha2::Digest + hkdf::hmac::EagerHash,
+{
+    let mut out = Zeroizing::new(vec![0; len]);
+    let len = u16::try_from(len).expect("key schedule output length fits in a u16");
+    prk.expand_multi_info(
+        &[&len.to_be_bytes(), VERSION_LABEL, suite_id, label, context],
+        &mut out,
+    )
+    .expect("key schedule output length is valid for the kdf");
+    out
+}
+
+#[cfg(feature = "shake")]
+fn one_stage<H>(
+    suite_id: &[u8],
+    mode: &ModeInput<'_>,
+    shared_secret: &[u8],
+    info: &[u8],
+    lengths: Lengths,
+) -> KeySchedule
+where
+    H: Default + shake::Update + shake::ExtendableOutput,
+{
+    use shake::XofReader;
+
+    // the variable-length inputs were checked by `Kdf::check_key_schedule_input`, and the
+    // others are at most a few hundred bytes
+    fn len_u16(buf: &[u8]) -> [u8; 2] {
+        u16::try_from(buf.len())
+            .expect("checked key schedule input length fits in a u16")
+            .to_be_bytes()
+    }
+
+    let total = lengths.key + lengths.base_nonce + lengths.exporter_secret;
+    let mut digest = Zeroizing::new(vec![0; total]);
+
+    let mut hasher = H::default();
+    hasher.update(&len_u16(mode.psk));
+    hasher.update(mode.psk);
+    hasher.update(&len_u16(shared_secret));
+    hasher.update(shared_secret);
+    hasher.update(VERSION_LABEL);
+    hasher.update(suite_id);
+    hasher.update(&len_u16(b"secret"));
+    hasher.update(b"secret");
+    hasher.update(&len_u16(&digest));
+    hasher.update(&[mode.mode as u8]);
+    hasher.update(&len_u16(mode.psk_id));
+    hasher.update(mode.psk_id);
+    hasher.update(&len_u16(info));
+    hasher.update(info);
+    hasher.finalize_xof().read(&mut digest);
+
+    let (key, rest) = digest.split_at(lengths.key);
+    let (base_nonce, exporter_secret) = rest.split_at(lengths.base_nonce);
+
+    KeySchedule {
+        key: Zeroizing::new(key.to_vec()),
+        base_nonce: Zeroizing::new(base_nonce.to_vec()),
+        exporter_secret: Zeroizing::new(exporter_secret.to_vec()),
+    }
+}
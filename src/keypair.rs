@@ -1,17 +1,42 @@
 use hpke::Serializable;
+use subtle::ConstantTimeEq;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
 
-use crate::Kem;
+use crate::{deserialize_private_key, Error, Kem};
 
-/// An encoded keypair
-#[derive(Debug, Clone, Eq, PartialEq, zeroize::Zeroize)]
+/// An encoded keypair. The private key is zeroized when it is dropped, and is omitted from the
+/// [`Debug`] output, and private keys are compared in constant time.
+#[derive(Clone, Eq, Zeroize)]
 pub struct Keypair {
     /// the public key for this keypair
     pub public_key: Vec<u8>,
 
     /// the private key for this keypair,
-    pub private_key: Vec<u8>,
+    pub private_key: Zeroizing<Vec<u8>>,
 }
 
+impl std::fmt::Debug for Keypair {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Keypair")
+            .field("public_key", &self.public_key)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for Keypair {
+    fn eq(&self, other: &Self) -> bool {
+        self.public_key == other.public_key
+            && bool::from(
+                self.private_key
+                    .as_slice()
+                    .ct_eq(other.private_key.as_slice()),
+            )
+    }
+}
+
+// the private key is the only secret, and `Zeroizing` zeroizes it on drop
+impl ZeroizeOnDrop for Keypair {}
+
 impl Keypair {
     /// generate a keypair from a [`Kem`]
     #[must_use]
@@ -21,9 +46,26 @@ impl Keypair {
 }
 
 impl Keypair {
+    /// Check that this is a valid keypair for the provided [`Kem`]: that the private key can be
+    /// deserialized, and that the public key is the one that corresponds to it.
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if:
+    ///
+    /// * we are unable to deserialize the private key
+    /// * the public key [does not match](Error::KeypairMismatch) the private key
+    pub fn validate(&self, kem: Kem) -> Result<(), Error> {
+        if public_key_from_private(kem, &self.private_key)? == self.public_key {
+            Ok(())
+        } else {
+            Err(Error::KeypairMismatch)
+        }
+    }
+
     /// deconstructs the pair into (private, public)
     #[must_use]
-    pub fn into_parts(self) -> (Vec<u8>, Vec<u8>) {
+    pub fn into_parts(self) -> (Zeroizing<Vec<u8>>, Vec<u8>) {
         (self.private_key, self.public_key)
     }
 }
@@ -64,10 +106,107 @@ pub fn gen_keypair(kem: Kem) -> Keypair {
 fn gen_kp<KemT: hpke::kem::Kem>() -> Keypair {
     let (private_key, public_key) = KemT::gen_keypair();
     let public_key = public_key.to_bytes().to_vec();
-    let private_key = private_key.to_bytes().to_vec();
+    let private_key = Zeroizing::new(private_key.to_bytes().to_vec());
 
     Keypair {
         public_key,
         private_key,
     }
 }
+
+/// Deterministically derive a Keypair for the provided asymmetric key encapsulation mechanism
+/// ([`Kem`]) from input keying material, using the `DeriveKeyPair` function described in
+/// [RFC9180§7.1.3][1] and [draft-ietf-hpke-pq][2].
+///
+/// The same `ikm` always produces the same keypair, so it must be kept as secret as the private
+/// key, and should have at least as much entropy as the private key is long.
+///
+/// [1]: https://www.rfc-editor.org/rfc/rfc9180.html#section-7.1.3
+/// [2]: https://datatracker.ietf.org/doc/draft-ietf-hpke-pq/
+///
+/// # Errors
+///
+/// This will return a `Result::Err` variant if `ikm` is longer than 65535 bytes and the selected
+/// [`Kem`] is post-quantum or hybrid, as these derive keypairs with a SHAKE256 `LabeledDerive`
+/// that encodes the length of `ikm` in two bytes.
+pub fn derive_keypair(kem: Kem, ikm: &[u8]) -> Result<Keypair, Error> {
+    let max_ikm_len = usize::from(u16::MAX);
+    if kem.quantum_resistance().is_quantum_resistant() && ikm.len() > max_ikm_len {
+        return Err(Error::InvalidIkm { kem });
+    }
+
+    match kem {
+        #[cfg(feature = "nistp")]
+        Kem::DhP256HkdfSha256 => derive_kp::<hpke::kem::DhP256HkdfSha256>(ikm),
+
+        #[cfg(feature = "nistp")]
+        Kem::DhP384HkdfSha384 => derive_kp::<hpke::kem::DhP384HkdfSha384>(ikm),
+
+        #[cfg(feature = "nistp")]
+        Kem::DhP521HkdfSha512 => derive_kp::<hpke::kem::DhP521HkdfSha512>(ikm),
+
+        #[cfg(feature = "x25519")]
+        Kem::X25519HkdfSha256 => derive_kp::<hpke::kem::X25519HkdfSha256>(ikm),
+
+        #[cfg(feature = "mlkem")]
+        Kem::MlKem768 => derive_kp::<hpke::kem::MlKem768>(ikm),
+
+        #[cfg(feature = "mlkem")]
+        Kem::MlKem1024 => derive_kp::<hpke::kem::MlKem1024>(ikm),
+
+        #[cfg(all(feature = "mlkem", feature = "x25519"))]
+        Kem::XWing => derive_kp::<hpke::kem::XWing>(ikm),
+
+        #[cfg(all(feature = "mlkem", feature = "nistp"))]
+        Kem::MlKem768P256 => derive_kp::<hpke::kem::MlKem768P256>(ikm),
+
+        #[cfg(all(feature = "mlkem", feature = "nistp"))]
+        Kem::MlKem1024P384 => derive_kp::<hpke::kem::MlKem1024P384>(ikm),
+    }
+}
+
+fn derive_kp<KemT: hpke::kem::Kem>(ikm: &[u8]) -> Result<Keypair, Error> {
+    let (private_key, public_key) = KemT::derive_keypair(ikm);
+    Ok(Keypair {
+        public_key: public_key.to_bytes().to_vec(),
+        private_key: Zeroizing::new(private_key.to_bytes().to_vec()),
+    })
+}
+
+/// compute the encoded public key corresponding to an encoded private key for a [`Kem`]
+pub(crate) fn public_key_from_private(kem: Kem, private_key: &[u8]) -> Result<Vec<u8>, Error> {
+    let public_key = match kem {
+        #[cfg(feature = "nistp")]
+        Kem::DhP256HkdfSha256 => pk_from_sk::<hpke::kem::DhP256HkdfSha256>(private_key),
+
+        #[cfg(feature = "nistp")]
+        Kem::DhP384HkdfSha384 => pk_from_sk::<hpke::kem::DhP384HkdfSha384>(private_key),
+
+        #[cfg(feature = "nistp")]
+        Kem::DhP521HkdfSha512 => pk_from_sk::<hpke::kem::DhP521HkdfSha512>(private_key),
+
+        #[cfg(feature = "x25519")]
+        Kem::X25519HkdfSha256 => pk_from_sk::<hpke::kem::X25519HkdfSha256>(private_key),
+
+        #[cfg(feature = "mlkem")]
+        Kem::MlKem768 => pk_from_sk::<hpke::kem::MlKem768>(private_key),
+
+        #[cfg(feature = "mlkem")]
+        Kem::MlKem1024 => pk_from_sk::<hpke::kem::MlKem1024>(private_key),
+
+        #[cfg(all(feature = "mlkem", feature = "x25519"))]
+        Kem::XWing => pk_from_sk::<hpke::kem::XWing>(private_key),
+
+        #[cfg(all(feature = "mlkem", feature = "nistp"))]
+        Kem::MlKem768P256 => pk_from_sk::<hpke::kem::MlKem768P256>(private_key),
+
+        #[cfg(all(feature = "mlkem", feature = "nistp"))]
+        Kem::MlKem1024P384 => pk_from_sk::<hpke::kem::MlKem1024P384>(private_key),
+    }?;
+    Ok(public_key)
+}
+
+fn pk_from_sk<KemT: hpke::kem::Kem>(private_key: &[u8]) -> Result<Vec<u8>, Error> {
+    let private_key = deserialize_private_key::<KemT>(private_key)?;
+    Ok(KemT::sk_to_pk(&private_key).to_bytes().to_vec())
+}
@@ -0,0 +1,244 @@
+use zeroize::Zeroizing;
+
+use crate::{
+    deserialize_encapped_key, deserialize_private_key, deserialize_public_key,
+    keypair::public_key_from_private, Error, Kem,
+};
+
+/// An encoded public key that has been checked to be valid for its [`Kem`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PublicKey {
+    kem: Kem,
+    bytes: Vec<u8>,
+}
+
+impl PublicKey {
+    /// Check that `bytes` is a valid encoded public key for `kem`.
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if we are unable to deserialize the public key.
+    pub fn new(kem: Kem, bytes: &[u8]) -> Result<Self, Error> {
+        check(kem, KeyKind::Public, bytes)?;
+        Ok(Self {
+            kem,
+            bytes: bytes.to_vec(),
+        })
+    }
+
+    /// the [`Kem`] this key is for
+    #[must_use]
+    pub fn kem(&self) -> Kem {
+        self.kem
+    }
+
+    /// the encoded key
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+/// An encoded private key that has been checked to be valid for its [`Kem`]. The encoded key is
+/// zeroized when dropped.
+#[derive(Clone)]
+pub struct PrivateKey {
+    kem: Kem,
+    bytes: Zeroizing<Vec<u8>>,
+}
+
+impl PrivateKey {
+    /// Check that `bytes` is a valid encoded private key for `kem`.
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if we are unable to deserialize the private key.
+    pub fn new(kem: Kem, bytes: &[u8]) -> Result<Self, Error> {
+        check(kem, KeyKind::Private, bytes)?;
+        Ok(Self {
+            kem,
+            bytes: Zeroizing::new(bytes.to_vec()),
+        })
+    }
+
+    /// the [`Kem`] this key is for
+    #[must_use]
+    pub fn kem(&self) -> Kem {
+        self.kem
+    }
+
+    /// the encoded key
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// the [`PublicKey`] corresponding to this private key
+    #[must_use]
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey {
+            kem: self.kem,
+            bytes: public_key_from_private(self.kem, &self.bytes)
+                .expect("private key was checked on construction"),
+        }
+    }
+}
+
+impl std::fmt::Debug for PrivateKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PrivateKey")
+            .field("kem", &self.kem)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An encapsulated key that has been checked to be valid for its [`Kem`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct EncappedKey {
+    kem: Kem,
+    bytes: Vec<u8>,
+}
+
+impl EncappedKey {
+    /// Check that `bytes` is a valid encapsulated key for `kem`.
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if we are unable to deserialize the encapsulated
+    /// key.
+    pub fn new(kem: Kem, bytes: &[u8]) -> Result<Self, Error> {
+        check(kem, KeyKind::Encapped, bytes)?;
+        Ok(Self {
+            kem,
+            bytes: bytes.to_vec(),
+        })
+    }
+
+    /// the [`Kem`] this key is for
+    #[must_use]
+    pub fn kem(&self) -> Kem {
+        self.kem
+    }
+
+    /// the encoded key
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+/// A public key argument to the [`Config`](crate::Config) methods: either a [`PublicKey`], whose
+/// [`Kem`] is checked against the [`Config`](crate::Config), or an encoded public key, which is
+/// deserialized when it is used.
+pub trait AsPublicKey {
+    /// the encoded public key, if it can be used with `kem`
+    fn public_key_bytes(&self, kem: Kem) -> Result<&[u8], Error>;
+}
+
+/// A private key argument to the [`Config`](crate::Config) methods: either a [`PrivateKey`],
+/// whose [`Kem`] is checked against the [`Config`](crate::Config), or an encoded private key,
+/// which is deserialized when it is used.
+pub trait AsPrivateKey {
+    /// the encoded private key, if it can be used with `kem`
+    fn private_key_bytes(&self, kem: Kem) -> Result<&[u8], Error>;
+}
+
+/// An encapsulated key argument to the [`Config`](crate::Config) methods: either an
+/// [`EncappedKey`], whose [`Kem`] is checked against the [`Config`](crate::Config), or an encoded
+/// encapsulated key, which is deserialized when it is used.
+pub trait AsEncappedKey {
+    /// the encoded encapsulated key, if it can be used with `kem`
+    fn encapped_key_bytes(&self, kem: Kem) -> Result<&[u8], Error>;
+}
+
+macro_rules! impl_key_traits {
+    ($typed:ty, $trait:ident, $fn:ident) => {
+        impl $trait for $typed {
+            fn $fn(&self, kem: Kem) -> Result<&[u8], Error> {
+                if self.kem == kem {
+                    Ok(&self.bytes)
+                } else {
+                    Err(Error::KemMismatch {
+                        expected: kem,
+                        found: self.kem,
+                    })
+                }
+            }
+        }
+
+        impl $trait for [u8] {
+            fn $fn(&self, _kem: Kem) -> Result<&[u8], Error> {
+                Ok(self)
+            }
+        }
+
+        impl $trait for Vec<u8> {
+            fn $fn(&self, _kem: Kem) -> Result<&[u8], Error> {
+                Ok(self)
+            }
+        }
+
+        impl<const N: usize> $trait for [u8; N] {
+            fn $fn(&self, _kem: Kem) -> Result<&[u8], Error> {
+                Ok(self)
+            }
+        }
+    };
+}
+
+impl_key_traits!(PublicKey, AsPublicKey, public_key_bytes);
+impl_key_traits!(PrivateKey, AsPrivateKey, private_key_bytes);
+impl_key_traits!(EncappedKey, AsEncappedKey, encapped_key_bytes);
+
+impl AsPrivateKey for Zeroizing<Vec<u8>> {
+    fn private_key_bytes(&self, _kem: Kem) -> Result<&[u8], Error> {
+        Ok(self)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum KeyKind {
+    Public,
+    Private,
+    Encapped,
+}
+
+fn check(kem: Kem, kind: KeyKind, bytes: &[u8]) -> Result<(), Error> {
+    match kem {
+        #[cfg(feature = "nistp")]
+        Kem::DhP256HkdfSha256 => check_kem::<hpke::kem::DhP256HkdfSha256>(kind, bytes),
+
+        #[cfg(feature = "nistp")]
+        Kem::DhP384HkdfSha384 => check_kem::<hpke::kem::DhP384HkdfSha384>(kind, bytes),
+
+        #[cfg(feature = "nistp")]
+        Kem::DhP521HkdfSha512 => check_kem::<hpke::kem::DhP521HkdfSha512>(kind, bytes),
+
+        #[cfg(feature = "x25519")]
+        Kem::X25519HkdfSha256 => check_kem::<hpke::kem::X25519HkdfSha256>(kind, bytes),
+
+        #[cfg(feature = "mlkem")]
+        Kem::MlKem768 => check_kem::<hpke::kem::MlKem768>(kind, bytes),
+
+        #[cfg(feature = "mlkem")]
+        Kem::MlKem1024 => check_kem::<hpke::kem::MlKem1024>(kind, bytes),
+
+        #[cfg(all(feature = "mlkem", feature = "x25519"))]
+        Kem::XWing => check_kem::<hpke::kem::XWing>(kind, bytes),
+
+        #[cfg(all(feature = "mlkem", feature = "nistp"))]
+        Kem::MlKem768P256 => check_kem::<hpke::kem::MlKem768P256>(kind, bytes),
+
+        #[cfg(all(feature = "mlkem", feature = "nistp"))]
+        Kem::MlKem1024P384 => check_kem::<hpke::kem::MlKem1024P384>(kind, bytes),
+    }?;
+    Ok(())
+}
+
+fn check_kem<KemT: hpke::kem::Kem>(kind: KeyKind, bytes: &[u8]) -> Result<(), Error> {
+    match kind {
+        KeyKind::Public => deserialize_public_key::<KemT>(bytes).map(drop),
+        KeyKind::Private => deserialize_private_key::<KemT>(bytes).map(drop),
+        KeyKind::Encapped => deserialize_encapped_key::<KemT>(bytes).map(drop),
+    }
+}
@@ -11,7 +11,8 @@
 #![allow(
     clippy::missing_errors_doc,
     clippy::use_self,
-    clippy::multiple_crate_versions
+    clippy::multiple_crate_versions,
+    clippy::too_many_arguments
 )]
 #![doc = include_str!("../README.md")]
 #![cfg(all(
@@ -20,22 +21,82 @@
     any(feature = "nistp", feature = "x25519", feature = "mlkem",),
 ))]
 
-use hpke::{Deserializable, HpkeError};
+use hpke::{Deserializable, HpkeError, PskBundle};
 
 #[macro_use]
 mod macros;
 
+mod auth_mode_open;
+pub use auth_mode_open::auth_mode_open;
+
+mod auth_mode_seal;
+pub use auth_mode_seal::auth_mode_seal;
+
+mod auth_psk_mode_open;
+pub use auth_psk_mode_open::auth_psk_mode_open;
+
+mod auth_psk_mode_seal;
+pub use auth_psk_mode_seal::auth_psk_mode_seal;
+
 mod base_mode_open;
-pub use base_mode_open::base_mode_open;
+pub use base_mode_open::{base_mode_open, base_mode_open_zeroizing};
 
 mod base_mode_seal;
 pub use base_mode_seal::base_mode_seal;
 
+mod psk_mode_open;
+pub use psk_mode_open::psk_mode_open;
+
+mod psk_mode_seal;
+pub use psk_mode_seal::psk_mode_seal;
+
 mod config;
 pub use config::Config;
 
+mod context;
+pub use context::{setup_receiver, setup_sender, ReceiverContext, SenderContext};
+
+mod error;
+pub use error::Error;
+
+mod mode;
+pub use mode::Mode;
+
+mod export;
+pub use export::{receiver_export, sender_export, EncappedKeyAndSecret};
+
+mod key_schedule;
+
+mod stream;
+pub use stream::{OpeningReader, SealingWriter, DEFAULT_CHUNK_SIZE, MAX_CHUNK_SIZE};
+
+#[cfg(feature = "tokio")]
+mod async_stream;
+#[cfg(feature = "tokio")]
+pub use async_stream::{AsyncOpeningReader, AsyncSealingWriter};
+
 mod keypair;
-pub use keypair::{gen_keypair, Keypair};
+pub use keypair::{derive_keypair, gen_keypair, Keypair};
+
+mod keys;
+pub use keys::{AsEncappedKey, AsPrivateKey, AsPublicKey, EncappedKey, PrivateKey, PublicKey};
+
+#[cfg(feature = "pkcs8")]
+mod pkix;
+
+#[cfg(all(
+    feature = "cose",
+    any(feature = "nistp", feature = "x25519", feature = "mlkem")
+))]
+mod cose;
+
+#[cfg(feature = "jwk")]
+mod jwk;
+#[cfg(feature = "jwk")]
+pub use jwk::Jwk;
+
+#[cfg(all(feature = "ssh", any(feature = "nistp", feature = "x25519")))]
+mod ssh;
 
 mod ciphertext;
 pub use ciphertext::EncappedKeyAndCiphertext;
@@ -49,21 +110,87 @@ pub use kdf::{Kdf, KDF_ALL};
 mod kem;
 pub use kem::{Kem, KEM_ALL};
 
-/// A simple error type for failed id lookups
-#[derive(Copy, Clone, Debug)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde_crate::Serialize, serde_crate::Deserialize)
-)]
-#[cfg_attr(feature = "serde", serde(crate = "serde_crate"))]
-pub struct IdLookupError(&'static str);
-impl std::fmt::Display for IdLookupError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_fmt(format_args!("id lookup error {}", self.0))
+mod id_lookup;
+pub use id_lookup::{AlgorithmKind, IdLookupError, LookupValue};
+
+mod policy;
+pub use policy::{Policy, PolicyViolation, QuantumResistance};
+
+/// the [`Kem`] that selects `KemT`
+fn kem_of<KemT: hpke::kem::Kem>() -> Kem {
+    Kem::try_from(KemT::KEM_ID).expect("every hpke kem has a Kem variant")
+}
+
+/// the [`Kdf`] that selects `KdfT`
+fn kdf_of<KdfT: hpke::kdf::Kdf>() -> Kdf {
+    Kdf::try_from(KdfT::KDF_ID).expect("every hpke kdf has a Kdf variant")
+}
+
+/// the [`Aead`] that selects `AeadT`
+fn aead_of<AeadT: hpke::aead::Aead>() -> Aead {
+    Aead::try_from(AeadT::AEAD_ID).expect("every hpke aead has an Aead variant")
+}
+
+pub(crate) fn deserialize_public_key<KemT: hpke::kem::Kem>(
+    encoded: &[u8],
+) -> Result<KemT::PublicKey, Error> {
+    KemT::PublicKey::from_bytes(encoded).map_err(|_| Error::InvalidPublicKey {
+        kem: kem_of::<KemT>(),
+    })
+}
+
+pub(crate) fn deserialize_private_key<KemT: hpke::kem::Kem>(
+    encoded: &[u8],
+) -> Result<KemT::PrivateKey, Error> {
+    KemT::PrivateKey::from_bytes(encoded).map_err(|_| Error::InvalidPrivateKey {
+        kem: kem_of::<KemT>(),
+    })
+}
+
+pub(crate) fn deserialize_encapped_key<KemT: hpke::kem::Kem>(
+    encoded: &[u8],
+) -> Result<KemT::EncappedKey, Error> {
+    KemT::EncappedKey::from_bytes(encoded).map_err(|_| Error::InvalidEncappedKey {
+        kem: kem_of::<KemT>(),
+    })
+}
+
+/// attribute an error from encapsulation or sealing to the [`Config`] component responsible
+pub(crate) fn seal_error<KemT: hpke::kem::Kem>(error: HpkeError) -> Error {
+    match error {
+        HpkeError::EncapError => Error::InvalidPublicKey {
+            kem: kem_of::<KemT>(),
+        },
+        error => Error::Hpke(error),
     }
 }
-impl std::error::Error for IdLookupError {}
 
-pub(crate) fn from_bytes<T: Deserializable>(encoded: &[u8]) -> Result<T, HpkeError> {
-    T::from_bytes(encoded)
+/// attribute an error from decapsulation or opening to the [`Config`] component responsible
+pub(crate) fn open_error<AeadT: hpke::aead::Aead, KemT: hpke::kem::Kem>(error: HpkeError) -> Error {
+    match error {
+        HpkeError::DecapError => Error::InvalidEncappedKey {
+            kem: kem_of::<KemT>(),
+        },
+        HpkeError::OpenError => Error::DecryptionFailed {
+            aead: aead_of::<AeadT>(),
+        },
+        error => Error::Hpke(error),
+    }
+}
+
+/// The minimum length of a pre-shared key, as per [RFC9180§5.1.2][1].
+///
+/// [1]: https://www.rfc-editor.org/rfc/rfc9180.html#section-5.1.2
+pub const MIN_PSK_LEN: usize = 32;
+
+pub(crate) fn psk_bundle<'a, KdfT: hpke::kdf::Kdf>(
+    psk: &'a [u8],
+    psk_id: &'a [u8],
+) -> Result<PskBundle<'a>, Error> {
+    if psk.len() < MIN_PSK_LEN || psk_id.is_empty() {
+        return Err(Error::InvalidPsk {
+            kdf: kdf_of::<KdfT>(),
+        });
+    }
+    Ok(PskBundle::new(psk, psk_id)?)
 }
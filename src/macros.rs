@@ -13,6 +13,9 @@ macro_rules! match_algo {
             $crate::Aead::ChaCha20Poly1305 => {
                 match_algo!(@kdf, hpke::aead::ChaCha20Poly1305, $kdf, $kem, $fn)
             }
+            $crate::Aead::ExportOnly => {
+                match_algo!(@kdf, hpke::aead::ExportOnlyAead, $kdf, $kem, $fn)
+            }
         }
     };
 
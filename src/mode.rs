@@ -0,0 +1,32 @@
+/// An HPKE mode, as per [RFC9180§5][1].
+///
+/// [1]: https://www.rfc-editor.org/rfc/rfc9180.html#section-5
+#[non_exhaustive]
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_crate::Serialize, serde_crate::Deserialize)
+)]
+#[cfg_attr(feature = "serde", serde(crate = "serde_crate"))]
+pub enum Mode {
+    /// mode_base
+    Base = 0x00,
+    /// mode_psk
+    Psk = 0x01,
+    /// mode_auth
+    Auth = 0x02,
+    /// mode_auth_psk
+    AuthPsk = 0x03,
+}
+
+impl Mode {
+    /// whether this mode authenticates the sender with an asymmetric key, requiring a [`Kem`]
+    /// that supports `AuthEncap` and `AuthDecap`
+    ///
+    /// [`Kem`]: crate::Kem
+    #[must_use]
+    pub const fn is_auth(self) -> bool {
+        matches!(self, Mode::Auth | Mode::AuthPsk)
+    }
+}
@@ -0,0 +1,401 @@
+#[cfg(feature = "mlkem")]
+use pkcs8::der::{Reader, Tag, TagNumber, Tagged};
+use pkcs8::{
+    der::{
+        asn1::{BitStringRef, OctetStringRef},
+        pem::{LineEnding, PemLabel},
+        AnyRef, Decode, Document, Encode, SecretDocument,
+    },
+    spki::AlgorithmIdentifierRef,
+    ObjectIdentifier, PrivateKeyInfoRef, SubjectPublicKeyInfoRef,
+};
+use zeroize::Zeroizing;
+
+use crate::{keypair::public_key_from_private, Error, Kem, Keypair, PublicKey};
+
+/// How the private key of an [`Algorithm`] is encoded in the PKCS#8 `privateKey` octet string.
+#[derive(Clone, Copy)]
+enum PrivateKeyFormat {
+    /// a SEC1 `ECPrivateKey`, as described in [RFC5915](https://www.rfc-editor.org/rfc/rfc5915)
+    #[cfg(feature = "nistp")]
+    Sec1,
+
+    /// a `CurvePrivateKey`, as described in [RFC8410](https://www.rfc-editor.org/rfc/rfc8410)
+    #[cfg(feature = "x25519")]
+    CurvePrivateKey,
+
+    /// an `ML-KEM-PrivateKey`, as described in
+    /// [draft-ietf-lamps-kyber-certificates](https://datatracker.ietf.org/doc/draft-ietf-lamps-kyber-certificates/).
+    /// Keys are written in the `seed` form, and read from either the `seed` or the `both` form.
+    /// The expanded key of the `both` form must match the one derived from the seed.
+    #[cfg(feature = "mlkem")]
+    MlKemSeed,
+}
+
+/// The algorithm identifier and private key encoding of a [`Kem`].
+struct Algorithm {
+    kem: Kem,
+    oid: ObjectIdentifier,
+    /// the named curve, for `id-ecPublicKey` algorithms
+    curve: Option<ObjectIdentifier>,
+    format: PrivateKeyFormat,
+}
+
+#[cfg(feature = "nistp")]
+const ID_EC_PUBLIC_KEY: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.2.1");
+
+/// The [`Kem`]s that have a standard algorithm identifier. The hybrid [`Kem`]s do not.
+const ALGORITHMS: &[Algorithm] = &[
+    #[cfg(feature = "nistp")]
+    Algorithm {
+        kem: Kem::DhP256HkdfSha256,
+        oid: ID_EC_PUBLIC_KEY,
+        curve: Some(ObjectIdentifier::new_unwrap("1.2.840.10045.3.1.7")),
+        format: PrivateKeyFormat::Sec1,
+    },
+    #[cfg(feature = "nistp")]
+    Algorithm {
+        kem: Kem::DhP384HkdfSha384,
+        oid: ID_EC_PUBLIC_KEY,
+        curve: Some(ObjectIdentifier::new_unwrap("1.3.132.0.34")),
+        format: PrivateKeyFormat::Sec1,
+    },
+    #[cfg(feature = "nistp")]
+    Algorithm {
+        kem: Kem::DhP521HkdfSha512,
+        oid: ID_EC_PUBLIC_KEY,
+        curve: Some(ObjectIdentifier::new_unwrap("1.3.132.0.35")),
+        format: PrivateKeyFormat::Sec1,
+    },
+    #[cfg(feature = "x25519")]
+    Algorithm {
+        kem: Kem::X25519HkdfSha256,
+        oid: ObjectIdentifier::new_unwrap("1.3.101.110"),
+        curve: None,
+        format: PrivateKeyFormat::CurvePrivateKey,
+    },
+    #[cfg(feature = "mlkem")]
+    Algorithm {
+        kem: Kem::MlKem768,
+        oid: ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.4.2"),
+        curve: None,
+        format: PrivateKeyFormat::MlKemSeed,
+    },
+    #[cfg(feature = "mlkem")]
+    Algorithm {
+        kem: Kem::MlKem1024,
+        oid: ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.4.3"),
+        curve: None,
+        format: PrivateKeyFormat::MlKemSeed,
+    },
+];
+
+impl Algorithm {
+    fn for_kem(kem: Kem) -> Result<&'static Self, Error> {
+        ALGORITHMS
+            .iter()
+            .find(|algorithm| algorithm.kem == kem)
+            .ok_or(Error::UnsupportedKeyEncoding { kem })
+    }
+
+    fn for_identifier(identifier: &AlgorithmIdentifierRef<'_>) -> Result<&'static Self, Error> {
+        let curve = match identifier.parameters {
+            Some(parameters) if !parameters.is_null() => {
+                Some(ObjectIdentifier::try_from(parameters).map_err(invalid)?)
+            }
+            _ => None,
+        };
+        ALGORITHMS
+            .iter()
+            .find(|algorithm| algorithm.oid == identifier.oid && algorithm.curve == curve)
+            .ok_or(Error::InvalidKeyEncoding)
+    }
+
+    fn identifier(&self) -> AlgorithmIdentifierRef<'_> {
+        AlgorithmIdentifierRef {
+            oid: self.oid,
+            parameters: self.curve.as_ref().map(AnyRef::from),
+        }
+    }
+
+    #[cfg_attr(not(feature = "nistp"), allow(unused_variables))]
+    fn encode_private_key(
+        &self,
+        private_key: &[u8],
+        public_key: &[u8],
+    ) -> Result<Zeroizing<Vec<u8>>, Error> {
+        let encoded = match self.format {
+            #[cfg(feature = "nistp")]
+            PrivateKeyFormat::Sec1 => sec1::EcPrivateKey {
+                private_key,
+                parameters: None,
+                public_key: Some(public_key),
+            }
+            .to_der(),
+
+            #[cfg(feature = "x25519")]
+            PrivateKeyFormat::CurvePrivateKey => {
+                OctetStringRef::new(private_key).and_then(|key| key.to_der())
+            }
+
+            #[cfg(feature = "mlkem")]
+            PrivateKeyFormat::MlKemSeed => {
+                AnyRef::new(ML_KEM_SEED_TAG, private_key).and_then(|seed| seed.to_der())
+            }
+        };
+        encoded.map(Zeroizing::new).map_err(invalid)
+    }
+
+    /// decode a private key, along with the public key if the encoding includes one
+    fn decode_private_key<'a>(
+        &self,
+        encoded: &'a [u8],
+    ) -> Result<(&'a [u8], Option<&'a [u8]>), Error> {
+        match self.format {
+            #[cfg(feature = "nistp")]
+            PrivateKeyFormat::Sec1 => {
+                let ec_private_key = sec1::EcPrivateKey::from_der(encoded).map_err(invalid)?;
+                if let Some(parameters) = ec_private_key.parameters {
+                    if parameters.named_curve() != self.curve {
+                        return Err(Error::InvalidKeyEncoding);
+                    }
+                }
+                Ok((ec_private_key.private_key, ec_private_key.public_key))
+            }
+
+            #[cfg(feature = "x25519")]
+            PrivateKeyFormat::CurvePrivateKey => {
+                let private_key = <&OctetStringRef>::from_der(encoded).map_err(invalid)?;
+                Ok((private_key.as_bytes(), None))
+            }
+
+            #[cfg(feature = "mlkem")]
+            PrivateKeyFormat::MlKemSeed => {
+                let private_key = AnyRef::from_der(encoded).map_err(invalid)?;
+                let seed = match private_key.tag() {
+                    ML_KEM_SEED_TAG => private_key.value(),
+                    Tag::Sequence => {
+                        let (seed, expanded) = private_key
+                            .sequence(|reader| {
+                                let seed = reader.decode::<&OctetStringRef>()?;
+                                let expanded = reader.decode::<&OctetStringRef>()?;
+                                Ok((seed.as_bytes(), expanded.as_bytes()))
+                            })
+                            .map_err(invalid)?;
+                        check_expanded_key(self.kem, seed, expanded)?;
+                        seed
+                    }
+                    _ => return Err(Error::InvalidKeyEncoding),
+                };
+                Ok((seed, None))
+            }
+        }
+    }
+}
+
+/// the `[0] IMPLICIT OCTET STRING` tag of the `seed` form of an `ML-KEM-PrivateKey`
+#[cfg(feature = "mlkem")]
+const ML_KEM_SEED_TAG: Tag = Tag::ContextSpecific {
+    constructed: false,
+    number: TagNumber(0),
+};
+
+/// Check that the expanded key of the `both` form of an `ML-KEM-PrivateKey` is the one derived
+/// from its seed.
+#[cfg(feature = "mlkem")]
+#[allow(deprecated)] // ml-kem deprecates the expanded form in favour of the seed
+fn check_expanded_key(kem: Kem, seed: &[u8], expanded: &[u8]) -> Result<(), Error> {
+    use ml_kem::{DecapsulationKey1024, DecapsulationKey768, ExpandedKeyEncoding, Seed};
+    use subtle::ConstantTimeEq;
+    use zeroize::Zeroize;
+
+    fn matches<K: ExpandedKeyEncoding>(key: K, expanded: &[u8]) -> bool {
+        let mut derived = key.to_expanded_bytes();
+        let matches = derived.as_slice().ct_eq(expanded).into();
+        derived.as_mut_slice().zeroize();
+        matches
+    }
+
+    let seed = Zeroizing::new(Seed::try_from(seed).map_err(|_| Error::InvalidPrivateKey { kem })?);
+    // only the ML-KEM algorithms use the `MlKemSeed` format
+    let matches = if kem == Kem::MlKem1024 {
+        matches(DecapsulationKey1024::from_seed(*seed), expanded)
+    } else {
+        matches(DecapsulationKey768::from_seed(*seed), expanded)
+    };
+    if matches {
+        Ok(())
+    } else {
+        Err(Error::KeypairMismatch)
+    }
+}
+
+fn invalid(_: pkcs8::der::Error) -> Error {
+    Error::InvalidKeyEncoding
+}
+
+fn decode_pem<T: PemLabel>(pem: &str) -> Result<SecretDocument, Error> {
+    let (label, document) = SecretDocument::from_pem(pem).map_err(invalid)?;
+    if label == T::PEM_LABEL {
+        Ok(document)
+    } else {
+        Err(Error::InvalidKeyEncoding)
+    }
+}
+
+impl Keypair {
+    /// Encode the private key of this [`Keypair`] as a DER PKCS#8 `PrivateKeyInfo`, as described
+    /// in [RFC5958](https://www.rfc-editor.org/rfc/rfc5958). For elliptic curve [`Kem`]s, the
+    /// public key is included.
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if:
+    ///
+    /// * `kem` has no standard PKCS#8 algorithm identifier
+    /// * the keypair is not a valid keypair for `kem`
+    pub fn to_pkcs8_der(&self, kem: Kem) -> Result<Zeroizing<Vec<u8>>, Error> {
+        Ok(self.pkcs8_document(kem)?.to_bytes())
+    }
+
+    /// Encode the private key of this [`Keypair`] as a PEM PKCS#8 `PrivateKeyInfo`, with the
+    /// `PRIVATE KEY` label.
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if:
+    ///
+    /// * `kem` has no standard PKCS#8 algorithm identifier
+    /// * the keypair is not a valid keypair for `kem`
+    pub fn to_pkcs8_pem(&self, kem: Kem) -> Result<Zeroizing<String>, Error> {
+        self.pkcs8_document(kem)?
+            .to_pem(PrivateKeyInfoRef::PEM_LABEL, LineEnding::LF)
+            .map_err(invalid)
+    }
+
+    /// Decode a DER PKCS#8 `PrivateKeyInfo`, returning the [`Kem`] identified by its algorithm
+    /// and the [`Keypair`]. The public key is derived from the private key.
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if:
+    ///
+    /// * the encoding is malformed, or its algorithm does not identify a supported [`Kem`]
+    /// * we are unable to deserialize the private key
+    /// * the encoding includes a public key that does not match the private key
+    pub fn from_pkcs8_der(der: &[u8]) -> Result<(Kem, Self), Error> {
+        let private_key_info = PrivateKeyInfoRef::from_der(der).map_err(invalid)?;
+        let algorithm = Algorithm::for_identifier(&private_key_info.algorithm)?;
+        let (private_key, embedded_public_key) =
+            algorithm.decode_private_key(private_key_info.private_key.as_bytes())?;
+
+        let public_key = public_key_from_private(algorithm.kem, private_key)?;
+        let embedded_public_key = match private_key_info.public_key {
+            Some(public_key) => Some(public_key.as_bytes().ok_or(Error::InvalidKeyEncoding)?),
+            None => embedded_public_key,
+        };
+        if embedded_public_key.is_some_and(|embedded| embedded != public_key) {
+            return Err(Error::KeypairMismatch);
+        }
+
+        Ok((
+            algorithm.kem,
+            Self {
+                private_key: Zeroizing::new(private_key.to_vec()),
+                public_key,
+            },
+        ))
+    }
+
+    /// Decode a PEM PKCS#8 `PrivateKeyInfo` with the `PRIVATE KEY` label, returning the [`Kem`]
+    /// identified by its algorithm and the [`Keypair`]. The public key is derived from the
+    /// private key.
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if:
+    ///
+    /// * the PEM is malformed or has a different label
+    /// * the encoding is malformed, or its algorithm does not identify a supported [`Kem`]
+    /// * we are unable to deserialize the private key
+    /// * the encoding includes a public key that does not match the private key
+    pub fn from_pkcs8_pem(pem: &str) -> Result<(Kem, Self), Error> {
+        Self::from_pkcs8_der(decode_pem::<PrivateKeyInfoRef<'_>>(pem)?.as_bytes())
+    }
+
+    fn pkcs8_document(&self, kem: Kem) -> Result<SecretDocument, Error> {
+        let algorithm = Algorithm::for_kem(kem)?;
+        self.validate(kem)?;
+        let private_key = algorithm.encode_private_key(&self.private_key, &self.public_key)?;
+        let private_key = OctetStringRef::new(&private_key).map_err(invalid)?;
+        SecretDocument::encode_msg(&PrivateKeyInfoRef::new(algorithm.identifier(), private_key))
+            .map_err(invalid)
+    }
+}
+
+impl PublicKey {
+    /// Decode a DER `SubjectPublicKeyInfo`, as described in
+    /// [RFC5280](https://www.rfc-editor.org/rfc/rfc5280#section-4.1.2.7), taking the [`Kem`] of
+    /// the [`PublicKey`] from its algorithm.
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if:
+    ///
+    /// * the encoding is malformed, or its algorithm does not identify a supported [`Kem`]
+    /// * we are unable to deserialize the public key
+    pub fn from_public_key_der(der: &[u8]) -> Result<Self, Error> {
+        let public_key_info = SubjectPublicKeyInfoRef::from_der(der).map_err(invalid)?;
+        let algorithm = Algorithm::for_identifier(&public_key_info.algorithm)?;
+        let public_key = public_key_info
+            .subject_public_key
+            .as_bytes()
+            .ok_or(Error::InvalidKeyEncoding)?;
+        Self::new(algorithm.kem, public_key)
+    }
+
+    /// Decode a PEM `SubjectPublicKeyInfo` with the `PUBLIC KEY` label, taking the [`Kem`] of
+    /// the [`PublicKey`] from its algorithm.
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if:
+    ///
+    /// * the PEM is malformed or has a different label
+    /// * the encoding is malformed, or its algorithm does not identify a supported [`Kem`]
+    /// * we are unable to deserialize the public key
+    pub fn from_public_key_pem(pem: &str) -> Result<Self, Error> {
+        Self::from_public_key_der(decode_pem::<SubjectPublicKeyInfoRef<'_>>(pem)?.as_bytes())
+    }
+
+    /// Encode this [`PublicKey`] as a DER `SubjectPublicKeyInfo`.
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if the [`Kem`] of this key has no standard
+    /// `SubjectPublicKeyInfo` algorithm identifier.
+    pub fn to_public_key_der(&self) -> Result<Vec<u8>, Error> {
+        Ok(self.public_key_document()?.into_vec())
+    }
+
+    /// Encode this [`PublicKey`] as a PEM `SubjectPublicKeyInfo`, with the `PUBLIC KEY` label.
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if the [`Kem`] of this key has no standard
+    /// `SubjectPublicKeyInfo` algorithm identifier.
+    pub fn to_public_key_pem(&self) -> Result<String, Error> {
+        self.public_key_document()?
+            .to_pem(SubjectPublicKeyInfoRef::PEM_LABEL, LineEnding::LF)
+            .map_err(invalid)
+    }
+
+    fn public_key_document(&self) -> Result<Document, Error> {
+        let algorithm = Algorithm::for_kem(self.kem())?;
+        Document::encode_msg(&SubjectPublicKeyInfoRef {
+            algorithm: algorithm.identifier(),
+            subject_public_key: BitStringRef::from_bytes(self.as_bytes()).map_err(invalid)?,
+        })
+        .map_err(invalid)
+    }
+}
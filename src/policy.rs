@@ -0,0 +1,173 @@
+use crate::{Aead, AlgorithmKind, Config, Error, Kdf, Kem};
+
+/// Whether an algorithm resists attack by a quantum computer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_crate::Serialize, serde_crate::Deserialize)
+)]
+#[cfg_attr(feature = "serde", serde(crate = "serde_crate"))]
+pub enum QuantumResistance {
+    /// secure only against a classical attacker
+    Classical,
+    /// secure against a quantum attacker
+    PostQuantum,
+    /// a combination of a classical and a post-quantum algorithm, secure against a quantum
+    /// attacker as long as the post-quantum component is, and against a classical attacker as
+    /// long as either component is
+    Hybrid,
+}
+
+impl QuantumResistance {
+    /// whether this is [`QuantumResistance::PostQuantum`] or [`QuantumResistance::Hybrid`]
+    #[must_use]
+    pub const fn is_quantum_resistant(self) -> bool {
+        matches!(
+            self,
+            QuantumResistance::PostQuantum | QuantumResistance::Hybrid
+        )
+    }
+}
+
+/// A set of rules restricting the [`Config`]s that may be used to seal and open messages.
+///
+/// The [`Default`] policy allows every [`Config`]. A policy is not enforced by the seal and open
+/// interfaces themselves: check a [`Config`] with [`Policy::check_seal`] before sealing, and
+/// with [`Policy::check_open`] before opening, including one built from the ids of a received
+/// message with [`Config::try_from_ids`].
+///
+/// [`Policy::require_quantum_resistance`] only applies to sealing, so that a policy can refuse
+/// classical-only [`Kem`]s for new messages while messages sealed before it was adopted can
+/// still be opened.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_crate::Serialize, serde_crate::Deserialize)
+)]
+#[cfg_attr(feature = "serde", serde(crate = "serde_crate"))]
+pub struct Policy {
+    /// the [`Aead`]s that may be used, or `None` to allow any
+    pub aeads: Option<Vec<Aead>>,
+    /// the [`Kdf`]s that may be used, or `None` to allow any
+    pub kdfs: Option<Vec<Kdf>>,
+    /// the [`Kem`]s that may be used, or `None` to allow any
+    pub kems: Option<Vec<Kem>>,
+    /// the lowest nominal security level in bits allowed for each algorithm, as reported by
+    /// [`Aead::security_level`], [`Kdf::security_level`] and [`Kem::security_level`]. The
+    /// [export-only](Aead::ExportOnly) [`Aead`] has no security level, so only the [`Kdf`] and
+    /// [`Kem`] of an export-only [`Config`] are checked against it.
+    pub min_security_level: u16,
+    /// whether sealing requires a [`Kem`] that is post-quantum or hybrid
+    pub require_quantum_resistance: bool,
+}
+
+impl Policy {
+    /// Check that this [`Policy`] allows sealing new messages with a [`Config`].
+    ///
+    /// # Errors
+    ///
+    /// This will return [`Error::PolicyViolation`] if the [`Config`] is not allowed.
+    pub fn check_seal(&self, config: &Config) -> Result<(), Error> {
+        self.check_open(config)?;
+        if self.require_quantum_resistance
+            && !config.kem.quantum_resistance().is_quantum_resistant()
+        {
+            return Err(PolicyViolation::NotQuantumResistant(config.kem).into());
+        }
+        Ok(())
+    }
+
+    /// Check that this [`Policy`] allows opening messages sealed with a [`Config`]. This applies
+    /// every rule except [`Policy::require_quantum_resistance`].
+    ///
+    /// # Errors
+    ///
+    /// This will return [`Error::PolicyViolation`] if the [`Config`] is not allowed.
+    pub fn check_open(&self, config: &Config) -> Result<(), Error> {
+        let Config { aead, kdf, kem } = *config;
+        if !allows(self.aeads.as_deref(), aead) {
+            return Err(PolicyViolation::AeadNotAllowed(aead).into());
+        }
+        if !allows(self.kdfs.as_deref(), kdf) {
+            return Err(PolicyViolation::KdfNotAllowed(kdf).into());
+        }
+        if !allows(self.kems.as_deref(), kem) {
+            return Err(PolicyViolation::KemNotAllowed(kem).into());
+        }
+
+        let levels = [
+            (AlgorithmKind::Aead, aead.security_level()),
+            (AlgorithmKind::Kdf, Some(kdf.security_level())),
+            (AlgorithmKind::Kem, Some(kem.security_level())),
+        ];
+        for (kind, level) in levels {
+            match level {
+                Some(level) if level < self.min_security_level => {
+                    return Err(PolicyViolation::SecurityLevelTooLow {
+                        kind,
+                        level,
+                        minimum: self.min_security_level,
+                    }
+                    .into())
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+fn allows<T: PartialEq>(allowlist: Option<&[T]>, algorithm: T) -> bool {
+    allowlist.is_none_or(|allowlist| allowlist.contains(&algorithm))
+}
+
+/// The rule of a [`Policy`] that a [`Config`] broke.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_crate::Serialize, serde_crate::Deserialize)
+)]
+#[cfg_attr(feature = "serde", serde(crate = "serde_crate"))]
+pub enum PolicyViolation {
+    /// the [`Aead`] is not in [`Policy::aeads`]
+    AeadNotAllowed(Aead),
+    /// the [`Kdf`] is not in [`Policy::kdfs`]
+    KdfNotAllowed(Kdf),
+    /// the [`Kem`] is not in [`Policy::kems`]
+    KemNotAllowed(Kem),
+    /// an algorithm is below [`Policy::min_security_level`]
+    SecurityLevelTooLow {
+        /// the kind of algorithm
+        kind: AlgorithmKind,
+        /// its nominal security level in bits
+        level: u16,
+        /// the minimum security level of the [`Policy`]
+        minimum: u16,
+    },
+    /// the [`Kem`] is classical-only, and [`Policy::require_quantum_resistance`] is set
+    NotQuantumResistant(Kem),
+}
+
+impl std::fmt::Display for PolicyViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PolicyViolation::AeadNotAllowed(aead) => write!(f, "{aead} is not allowed"),
+            PolicyViolation::KdfNotAllowed(kdf) => write!(f, "{kdf} is not allowed"),
+            PolicyViolation::KemNotAllowed(kem) => write!(f, "{kem} is not allowed"),
+            PolicyViolation::SecurityLevelTooLow {
+                kind,
+                level,
+                minimum,
+            } => write!(
+                f,
+                "{kind} security level of {level} bits is below the minimum of {minimum}"
+            ),
+            PolicyViolation::NotQuantumResistant(kem) => {
+                write!(f, "{kem} is not quantum resistant")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PolicyViolation {}
@@ -0,0 +1,71 @@
+use hpke::Serializable;
+
+use crate::{
+    deserialize_public_key, psk_bundle, seal_error, Config, EncappedKeyAndCiphertext, Error,
+};
+
+/// Single-shot HPKE ciphertext sealing in PSK mode.
+///
+/// `psk_mode_seal` provides an interface to [`hpke::single_shot_seal`] with
+/// [`hpke::OpModeS::Psk`] that does not require compile time selection of an algorithm. Instead,
+/// the selected algorithm is provided through the [`Config`] passed as the first argument.
+///
+/// The pre-shared key must be at least [`MIN_PSK_LEN`](crate::MIN_PSK_LEN) bytes long, as
+/// required by [RFC9180§5.1.2][1], and the pre-shared key id must not be empty.
+///
+/// [1]: https://www.rfc-editor.org/rfc/rfc9180.html#section-5.1.2
+///
+/// # Errors
+///
+/// This will return an `Result::Err` variant if:
+///
+/// * the selected [`Aead`](crate::Aead) is [export-only](crate::Aead::ExportOnly)
+/// * `info`, the pre-shared key or the pre-shared key id is too long for the selected
+///   [`Kdf`](crate::Kdf)
+/// * the pre-shared key is too short or the pre-shared key id is empty
+/// * we are unable to deserialize the recipient public key
+/// * there is an error in key encapsultion
+/// * there is an error in encryption
+pub fn psk_mode_seal(
+    config: &Config,
+    recipient_public_key: &[u8],
+    psk: &[u8],
+    psk_id: &[u8],
+    info: &[u8],
+    plaintext: &[u8],
+    aad: &[u8],
+) -> Result<EncappedKeyAndCiphertext, Error> {
+    let Config { aead, kdf, kem } = *config;
+    aead.check_encryption()?;
+    kdf.check_key_schedule_input(&[info, psk, psk_id])?;
+    let seal = match_algo!(aead, kdf, kem, seal);
+    seal(recipient_public_key, psk, psk_id, info, plaintext, aad)
+}
+
+fn seal<AeadT, KdfT, KemT>(
+    recipient_public_key: &[u8],
+    psk: &[u8],
+    psk_id: &[u8],
+    info: &[u8],
+    plaintext: &[u8],
+    aad: &[u8],
+) -> Result<EncappedKeyAndCiphertext, Error>
+where
+    AeadT: hpke::aead::Aead,
+    KdfT: hpke::kdf::Kdf,
+    KemT: hpke::kem::Kem,
+{
+    let (encapped_key, ciphertext) = hpke::single_shot_seal::<AeadT, KdfT, KemT>(
+        &hpke::OpModeS::Psk(psk_bundle::<KdfT>(psk, psk_id)?),
+        &deserialize_public_key::<KemT>(recipient_public_key)?,
+        info,
+        plaintext,
+        aad,
+    )
+    .map_err(seal_error::<KemT>)?;
+
+    Ok(EncappedKeyAndCiphertext {
+        encapped_key: encapped_key.to_bytes().to_vec(),
+        ciphertext,
+    })
+}
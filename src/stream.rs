@@ -0,0 +1,367 @@
+use std::io::{self, Read, Write};
+
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::{Config, Error, ReceiverContext, SenderContext};
+
+/// The number of plaintext bytes sealed into each chunk of a stream by [`SealingWriter::new`].
+pub const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+/// The largest number of plaintext bytes that can be sealed into a single chunk of a stream.
+pub const MAX_CHUNK_SIZE: usize = 16 * 1024 * 1024;
+
+const NON_FINAL_CHUNK: u8 = 0;
+const FINAL_CHUNK: u8 = 1;
+
+/// A stream starts with the length of the encapsulated key as a big-endian u16, followed by the
+/// encapsulated key.
+pub(crate) const HEADER_PREFIX_LEN: usize = 2;
+
+/// Each chunk of a stream is a one byte flag marking whether it is the final chunk, followed by
+/// the length of the chunk's ciphertext as a big-endian u32, followed by the ciphertext. The
+/// flag is also the chunk's associated data, so it cannot be altered, and the sequence number of
+/// the underlying context prevents chunks from being reordered.
+pub(crate) const CHUNK_PREFIX_LEN: usize = 5;
+
+/// The framing shared by the blocking and async stream sealers.
+#[derive(Debug)]
+pub(crate) struct StreamSealer {
+    context: SenderContext,
+}
+
+impl StreamSealer {
+    /// set up a sealer, returning the stream header along with it
+    pub(crate) fn new(
+        config: &Config,
+        recipient_public_key: &[u8],
+        info: &[u8],
+    ) -> Result<(Vec<u8>, Self), Error> {
+        config.aead.check_encryption()?;
+        let (encapped_key, context) = config.setup_sender(recipient_public_key, info)?;
+        let len = u16::try_from(encapped_key.len()).expect("encapsulated key length fits in a u16");
+        let header = [&len.to_be_bytes()[..], &encapped_key].concat();
+        Ok((header, Self { context }))
+    }
+
+    /// seal a chunk of at most [`MAX_CHUNK_SIZE`] bytes, returning the framed chunk
+    pub(crate) fn seal_chunk(&mut self, plaintext: &[u8], last: bool) -> Result<Vec<u8>, Error> {
+        let flag = if last { FINAL_CHUNK } else { NON_FINAL_CHUNK };
+        let ciphertext = self.context.seal(plaintext, &[flag])?;
+        let len = u32::try_from(ciphertext.len()).expect("chunk length fits in a u32");
+
+        let mut chunk = Vec::with_capacity(CHUNK_PREFIX_LEN + ciphertext.len());
+        chunk.push(flag);
+        chunk.extend_from_slice(&len.to_be_bytes());
+        chunk.extend_from_slice(&ciphertext);
+        Ok(chunk)
+    }
+}
+
+/// The framing shared by the blocking and async stream openers.
+#[derive(Debug)]
+pub(crate) struct StreamOpener {
+    context: ReceiverContext,
+    finished: bool,
+}
+
+impl StreamOpener {
+    pub(crate) fn new(
+        config: &Config,
+        private_key: &[u8],
+        encapped_key: &[u8],
+        info: &[u8],
+    ) -> Result<Self, Error> {
+        config.aead.check_encryption()?;
+        Ok(Self {
+            context: config.setup_receiver(private_key, encapped_key, info)?,
+            finished: false,
+        })
+    }
+
+    /// the length of the encapsulated key that follows the header prefix
+    pub(crate) fn encapped_key_len(prefix: [u8; HEADER_PREFIX_LEN]) -> usize {
+        u16::from_be_bytes(prefix).into()
+    }
+
+    /// whether the chunk is the final chunk, and the length of its ciphertext
+    pub(crate) fn chunk_len(&self, prefix: [u8; CHUNK_PREFIX_LEN]) -> Result<(bool, usize), Error> {
+        let [flag, len @ ..] = prefix;
+        let last = match flag {
+            NON_FINAL_CHUNK => false,
+            FINAL_CHUNK => true,
+            _ => return Err(Error::MalformedStream),
+        };
+        let len = usize::try_from(u32::from_be_bytes(len)).map_err(|_| Error::MalformedStream)?;
+        let tag_len = self.context.config().aead.n_t();
+        if !(tag_len..=MAX_CHUNK_SIZE + tag_len).contains(&len) {
+            return Err(Error::MalformedStream);
+        }
+        Ok((last, len))
+    }
+
+    pub(crate) fn open_chunk(
+        &mut self,
+        last: bool,
+        ciphertext: &[u8],
+    ) -> Result<Zeroizing<Vec<u8>>, Error> {
+        if self.finished {
+            return Err(Error::MalformedStream);
+        }
+        let flag = if last { FINAL_CHUNK } else { NON_FINAL_CHUNK };
+        let plaintext = Zeroizing::new(self.context.open(ciphertext, &[flag])?);
+        self.finished = last;
+        Ok(plaintext)
+    }
+
+    /// whether the final chunk has been opened
+    pub(crate) fn is_finished(&self) -> bool {
+        self.finished
+    }
+}
+
+/// Seals everything written to it into a stream of chunks that can be opened by an
+/// [`OpeningReader`], and writes the stream to an underlying [`Write`].
+///
+/// The stream starts with the encapsulated key, and each chunk holds up to a fixed number of
+/// plaintext bytes, sealed with a Base mode [`SenderContext`]. The last chunk is marked as
+/// final, so [`SealingWriter::finish`] must be called once all plaintext has been written:
+/// otherwise the stream will be rejected by the [`OpeningReader`] as truncated.
+///
+/// If any call returns an error, the stream is left in an unspecified state and must be
+/// abandoned.
+pub struct SealingWriter<W: Write> {
+    inner: W,
+    sealer: StreamSealer,
+    chunk_size: usize,
+    /// the plaintext of the next chunk, which is allocated up front so that it is never
+    /// reallocated, and zeroized once sealed
+    buffer: Zeroizing<Vec<u8>>,
+}
+
+impl<W: Write> SealingWriter<W> {
+    /// Set up a [`SealingWriter`] to `recipient_public_key` with [`DEFAULT_CHUNK_SIZE`] byte
+    /// chunks, and write the stream header to `inner`.
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if:
+    ///
+    /// * the selected [`Aead`](crate::Aead) is [export-only](crate::Aead::ExportOnly)
+    /// * we are unable to deserialize the recipient public key
+    /// * there is an error in key encapsultion
+    /// * `info` is too long for the selected [`Kdf`](crate::Kdf)
+    /// * there is an error writing the header to `inner`
+    pub fn new(
+        config: &Config,
+        recipient_public_key: &[u8],
+        info: &[u8],
+        inner: W,
+    ) -> io::Result<Self> {
+        Self::with_chunk_size(
+            config,
+            recipient_public_key,
+            info,
+            DEFAULT_CHUNK_SIZE,
+            inner,
+        )
+    }
+
+    /// Set up a [`SealingWriter`] to `recipient_public_key` with `chunk_size` byte chunks, and
+    /// write the stream header to `inner`.
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if:
+    ///
+    /// * `chunk_size` is zero or larger than [`MAX_CHUNK_SIZE`]
+    /// * the selected [`Aead`](crate::Aead) is [export-only](crate::Aead::ExportOnly)
+    /// * we are unable to deserialize the recipient public key
+    /// * there is an error in key encapsultion
+    /// * `info` is too long for the selected [`Kdf`](crate::Kdf)
+    /// * there is an error writing the header to `inner`
+    pub fn with_chunk_size(
+        config: &Config,
+        recipient_public_key: &[u8],
+        info: &[u8],
+        chunk_size: usize,
+        mut inner: W,
+    ) -> io::Result<Self> {
+        if !(1..=MAX_CHUNK_SIZE).contains(&chunk_size) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "chunk size must be between 1 and MAX_CHUNK_SIZE",
+            ));
+        }
+        let (header, sealer) =
+            StreamSealer::new(config, recipient_public_key, info).map_err(io::Error::other)?;
+        inner.write_all(&header)?;
+        Ok(Self {
+            inner,
+            sealer,
+            chunk_size,
+            buffer: Zeroizing::new(Vec::with_capacity(chunk_size)),
+        })
+    }
+
+    /// Seal any buffered plaintext into the final chunk, write it out and flush the underlying
+    /// writer, which is returned.
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if there is an error in encryption, or in writing
+    /// to or flushing the underlying writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.write_chunk(true)?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+
+    fn write_chunk(&mut self, last: bool) -> io::Result<()> {
+        let chunk = self
+            .sealer
+            .seal_chunk(&self.buffer, last)
+            .map_err(io::Error::other)?;
+        self.buffer.zeroize();
+        self.inner.write_all(&chunk)
+    }
+}
+
+impl<W: Write> Write for SealingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut remaining = buf;
+        while !remaining.is_empty() {
+            // a full chunk is only sealed once we know it is not the final one
+            if self.buffer.len() == self.chunk_size {
+                self.write_chunk(false)?;
+            }
+            let len = remaining.len().min(self.chunk_size - self.buffer.len());
+            let (taken, rest) = remaining.split_at(len);
+            self.buffer.extend_from_slice(taken);
+            remaining = rest;
+        }
+        Ok(buf.len())
+    }
+
+    /// Flushes the underlying writer. Plaintext is only written out once a full chunk has been
+    /// buffered, or when the stream is [finished](SealingWriter::finish).
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<W: Write> std::fmt::Debug for SealingWriter<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SealingWriter")
+            .field("sealer", &self.sealer)
+            .field("chunk_size", &self.chunk_size)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Opens a stream written by a [`SealingWriter`] from an underlying [`Read`], yielding the
+/// plaintext.
+///
+/// Each chunk is authenticated before any of its plaintext is returned. A stream that has been
+/// truncated, has had its chunks reordered or altered, or has data following its final chunk
+/// results in an error: [`io::ErrorKind::UnexpectedEof`] for truncation, or
+/// [`io::ErrorKind::InvalidData`] wrapping an [`Error`] otherwise. Plaintext returned before the
+/// error must not be trusted to be complete.
+pub struct OpeningReader<R: Read> {
+    inner: R,
+    opener: StreamOpener,
+    plaintext: Zeroizing<Vec<u8>>,
+    position: usize,
+    at_eof: bool,
+}
+
+impl<R: Read> OpeningReader<R> {
+    /// Read the stream header from `inner` and set up an [`OpeningReader`] with `private_key`.
+    ///
+    /// # Errors
+    ///
+    /// This will return a `Result::Err` variant if:
+    ///
+    /// * the selected [`Aead`](crate::Aead) is [export-only](crate::Aead::ExportOnly)
+    /// * there is an error reading the header from `inner`
+    /// * we are unable to deserialize the private key or encapsulated key
+    /// * there is an error in key decapsulation
+    /// * `info` is too long for the selected [`Kdf`](crate::Kdf)
+    pub fn new(config: &Config, private_key: &[u8], info: &[u8], mut inner: R) -> io::Result<Self> {
+        let mut prefix = [0; HEADER_PREFIX_LEN];
+        inner.read_exact(&mut prefix)?;
+        let mut encapped_key = vec![0; StreamOpener::encapped_key_len(prefix)];
+        inner.read_exact(&mut encapped_key)?;
+
+        Ok(Self {
+            inner,
+            opener: StreamOpener::new(config, private_key, &encapped_key, info)
+                .map_err(invalid_data)?,
+            plaintext: Zeroizing::default(),
+            position: 0,
+            at_eof: false,
+        })
+    }
+
+    /// Return the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn read_chunk(&mut self) -> io::Result<()> {
+        let mut prefix = [0; CHUNK_PREFIX_LEN];
+        self.inner.read_exact(&mut prefix)?;
+        let (last, len) = self.opener.chunk_len(prefix).map_err(invalid_data)?;
+        let mut ciphertext = vec![0; len];
+        self.inner.read_exact(&mut ciphertext)?;
+
+        self.plaintext = self
+            .opener
+            .open_chunk(last, &ciphertext)
+            .map_err(invalid_data)?;
+        self.position = 0;
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for OpeningReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        loop {
+            let remaining = &self.plaintext[self.position..];
+            if !remaining.is_empty() {
+                let len = remaining.len().min(buf.len());
+                buf[..len].copy_from_slice(&remaining[..len]);
+                self.position += len;
+                return Ok(len);
+            }
+
+            if self.opener.is_finished() {
+                while !self.at_eof {
+                    match self.inner.read(&mut [0]) {
+                        Ok(0) => self.at_eof = true,
+                        Ok(_) => return Err(invalid_data(Error::MalformedStream)),
+                        Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                        Err(e) => return Err(e),
+                    }
+                }
+                return Ok(0);
+            }
+
+            self.read_chunk()?;
+        }
+    }
+}
+
+impl<R: Read> std::fmt::Debug for OpeningReader<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OpeningReader")
+            .field("opener", &self.opener)
+            .finish_non_exhaustive()
+    }
+}
+
+pub(crate) fn invalid_data(error: Error) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
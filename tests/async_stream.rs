@@ -0,0 +1,139 @@
+#![cfg(feature = "tokio")]
+
+use std::io::{ErrorKind, Read, Write};
+
+use hpke_dispatch::*;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+mod common;
+use common::encrypting_configs;
+
+const APP_INFO: &[u8] = b"application info";
+const CHUNK_SIZE: usize = 16;
+
+async fn seal_stream(config: &Config, public_key: &[u8], plaintext: &[u8]) -> Vec<u8> {
+    let mut writer =
+        AsyncSealingWriter::with_chunk_size(config, public_key, APP_INFO, CHUNK_SIZE, Vec::new())
+            .unwrap_or_else(|e| panic!("problem with {config:?} AsyncSealingWriter ({e:?})"));
+    for piece in plaintext.chunks(7) {
+        writer.write_all(piece).await.unwrap();
+    }
+    writer.shutdown().await.unwrap();
+    writer.into_inner()
+}
+
+async fn open_stream(
+    config: &Config,
+    private_key: &[u8],
+    stream: &[u8],
+) -> std::io::Result<Vec<u8>> {
+    let mut reader = AsyncOpeningReader::new(config, private_key, APP_INFO, stream).await?;
+    let mut plaintext = Vec::new();
+    reader.read_to_end(&mut plaintext).await?;
+    Ok(plaintext)
+}
+
+#[tokio::test]
+async fn test_async_stream_round_trips() {
+    for config in encrypting_configs() {
+        let (private_key, public_key) = config.kem.gen_keypair().into_parts();
+        for len in [0, 1, CHUNK_SIZE, CHUNK_SIZE + 1, 5 * CHUNK_SIZE] {
+            let plaintext = (0..len).map(|i| i as u8).collect::<Vec<_>>();
+            let stream = seal_stream(&config, &public_key, &plaintext).await;
+            let opened = open_stream(&config, &private_key, &stream)
+                .await
+                .unwrap_or_else(|e| panic!("problem with {config:?} AsyncOpeningReader ({e:?})"));
+            assert_eq!(opened, plaintext, "stream did not match for {config:?}");
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_async_stream_over_duplex() {
+    let config = encrypting_configs().next().unwrap();
+    let (private_key, public_key) = config.kem.gen_keypair().into_parts();
+    let plaintext = (0..10 * CHUNK_SIZE + 3)
+        .map(|i| i as u8)
+        .collect::<Vec<_>>();
+
+    // a small pipe, so that both sides have to wait on each other
+    let (client, server) = tokio::io::duplex(8);
+    let write = async {
+        let mut writer =
+            AsyncSealingWriter::with_chunk_size(&config, &public_key, APP_INFO, CHUNK_SIZE, client)
+                .unwrap();
+        writer.write_all(&plaintext).await.unwrap();
+        writer.shutdown().await.unwrap();
+    };
+    let read = async {
+        let mut reader = AsyncOpeningReader::new(&config, &private_key, APP_INFO, server)
+            .await
+            .unwrap();
+        let mut opened = Vec::new();
+        reader.read_to_end(&mut opened).await.unwrap();
+        opened
+    };
+
+    let ((), opened) = tokio::join!(write, read);
+    assert_eq!(opened, plaintext);
+}
+
+#[tokio::test]
+async fn test_async_stream_blocking_interop() {
+    let config = encrypting_configs().next().unwrap();
+    let (private_key, public_key) = config.kem.gen_keypair().into_parts();
+    let plaintext = vec![0x5a; 3 * CHUNK_SIZE + 5];
+
+    let stream = seal_stream(&config, &public_key, &plaintext).await;
+    let mut opened = Vec::new();
+    OpeningReader::new(&config, &private_key, APP_INFO, &stream[..])
+        .unwrap()
+        .read_to_end(&mut opened)
+        .unwrap();
+    assert_eq!(opened, plaintext);
+
+    let mut writer =
+        SealingWriter::with_chunk_size(&config, &public_key, APP_INFO, CHUNK_SIZE, Vec::new())
+            .unwrap();
+    writer.write_all(&plaintext).unwrap();
+    let stream = writer.finish().unwrap();
+    assert_eq!(
+        open_stream(&config, &private_key, &stream).await.unwrap(),
+        plaintext
+    );
+}
+
+#[tokio::test]
+async fn test_async_stream_tampering() {
+    let config = encrypting_configs().next().unwrap();
+    let (private_key, public_key) = config.kem.gen_keypair().into_parts();
+    let stream = seal_stream(&config, &public_key, &[7; 3 * CHUNK_SIZE + 1]).await;
+
+    let truncated = open_stream(&config, &private_key, &stream[..stream.len() - 1])
+        .await
+        .unwrap_err();
+    assert_eq!(truncated.kind(), ErrorKind::UnexpectedEof);
+
+    let mut altered = stream.clone();
+    *altered.last_mut().unwrap() ^= 1;
+    let altered = open_stream(&config, &private_key, &altered)
+        .await
+        .unwrap_err();
+    assert_eq!(altered.kind(), ErrorKind::InvalidData);
+
+    let trailing = open_stream(&config, &private_key, &[&stream[..], &[0]].concat())
+        .await
+        .unwrap_err();
+    assert_eq!(trailing.kind(), ErrorKind::InvalidData);
+
+    // an unfinished writer leaves a truncated stream
+    let mut writer =
+        AsyncSealingWriter::with_chunk_size(&config, &public_key, APP_INFO, CHUNK_SIZE, Vec::new())
+            .unwrap();
+    writer.write_all(&[7; 2 * CHUNK_SIZE]).await.unwrap();
+    writer.flush().await.unwrap();
+    let unfinished = open_stream(&config, &private_key, &writer.into_inner())
+        .await
+        .unwrap_err();
+    assert_eq!(unfinished.kind(), ErrorKind::UnexpectedEof);
+}
@@ -0,0 +1,32 @@
+//! Helpers shared by the integration tests. Not every test uses all of them.
+#![allow(dead_code)]
+
+use hpke_dispatch::{Aead, Config, AEAD_ALL, KDF_ALL, KEM_ALL};
+
+/// every [`Aead`] except [export-only](Aead::ExportOnly)
+pub fn encrypting_aeads() -> Vec<Aead> {
+    AEAD_ALL
+        .iter()
+        .copied()
+        .filter(|aead| *aead != Aead::ExportOnly)
+        .collect()
+}
+
+/// every combination of an [`Aead`], a [`Kdf`](hpke_dispatch::Kdf) and a
+/// [`Kem`](hpke_dispatch::Kem)
+pub fn all_configs() -> impl Iterator<Item = Config> {
+    AEAD_ALL.iter().flat_map(|aead| {
+        KDF_ALL.iter().flat_map(move |kdf| {
+            KEM_ALL.iter().map(move |kem| Config {
+                aead: *aead,
+                kdf: *kdf,
+                kem: *kem,
+            })
+        })
+    })
+}
+
+/// every [`Config`] from [`all_configs`] that can seal and open messages
+pub fn encrypting_configs() -> impl Iterator<Item = Config> {
+    all_configs().filter(|config| config.aead != Aead::ExportOnly)
+}
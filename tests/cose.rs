@@ -0,0 +1,178 @@
+#![cfg(all(
+    feature = "cose",
+    feature = "nistp",
+    feature = "x25519",
+    feature = "mlkem"
+))]
+
+use hpke_dispatch::*;
+
+#[test]
+fn test_cose_key_round_trips() {
+    for kem in KEM_ALL {
+        let keypair = kem.gen_keypair();
+        let public_key = PublicKey::new(*kem, &keypair.public_key).unwrap();
+        // no COSE draft covers the hybrid kems yet
+        if matches!(kem, Kem::XWing | Kem::MlKem768P256 | Kem::MlKem1024P384) {
+            assert_eq!(
+                keypair.to_cose_key(*kem),
+                Err(Error::UnsupportedKeyEncoding { kem: *kem })
+            );
+            assert_eq!(
+                public_key.to_cose_key(),
+                Err(Error::UnsupportedKeyEncoding { kem: *kem })
+            );
+            continue;
+        }
+
+        let private_cose_key = keypair
+            .to_cose_key(*kem)
+            .unwrap_or_else(|e| panic!("problem with {kem:?} to_cose_key ({e:?})"));
+        assert_eq!(
+            Keypair::from_cose_key(&private_cose_key).unwrap(),
+            (*kem, keypair)
+        );
+        assert_eq!(
+            PublicKey::from_cose_key(&private_cose_key).unwrap(),
+            public_key
+        );
+
+        let public_cose_key = public_key.to_cose_key().unwrap();
+        assert_eq!(
+            PublicKey::from_cose_key(&public_cose_key).unwrap(),
+            public_key
+        );
+        assert_eq!(
+            Keypair::from_cose_key(&public_cose_key),
+            Err(Error::InvalidKeyEncoding)
+        );
+    }
+}
+
+#[test]
+fn test_cose_key_rfc9052() {
+    // the P-256 private key from RFC9052 Appendix C.7.2, with a kid
+    let cose_key = hex::decode(concat!(
+        "a6",
+        "0102",
+        "025824",
+        "6d65726961646f632e6272616e64796275636b406275636b6c616e642e6578616d706c65",
+        "2001",
+        "215820",
+        "65eda5a12577c2bae829437fe338701a10aaa375e1bb5b5de108de439c08551d",
+        "225820",
+        "1e52ed75701163f7f9e40ddf9f341b3dc9ba860af7e0ca7ca7e9eecd0084d19c",
+        "235820",
+        "aff907c99f9ad3aae6c4cdf21122bce2bd68b5283e6907154ad911840fa208cf",
+    ))
+    .unwrap();
+
+    let (kem, keypair) = Keypair::from_cose_key(&cose_key).unwrap();
+    assert_eq!(kem, Kem::DhP256HkdfSha256);
+    assert_eq!(
+        hex::encode(&keypair.private_key),
+        "aff907c99f9ad3aae6c4cdf21122bce2bd68b5283e6907154ad911840fa208cf"
+    );
+
+    // the kid is not retained, and parameters are written in deterministic order
+    let mut expected = cose_key.clone();
+    expected.drain(3..3 + 3 + 36);
+    expected[0] = 0xa4;
+    assert_eq!(
+        PublicKey::from_cose_key(&cose_key)
+            .unwrap()
+            .to_cose_key()
+            .unwrap(),
+        expected[..expected.len() - 35]
+    );
+    expected[0] = 0xa5;
+    assert_eq!(*keypair.to_cose_key(kem).unwrap(), expected);
+}
+
+#[test]
+fn test_cose_key_x25519_encoding() {
+    let keypair = Kem::X25519HkdfSha256.gen_keypair();
+    let public_key = PublicKey::new(Kem::X25519HkdfSha256, &keypair.public_key).unwrap();
+    assert_eq!(
+        public_key.to_cose_key().unwrap(),
+        [
+            &[0xa3, 0x01, 0x01, 0x20, 0x04, 0x21, 0x58, 0x20],
+            &keypair.public_key[..]
+        ]
+        .concat()
+    );
+}
+
+#[test]
+fn test_cose_key_ml_kem_encoding() {
+    let keypair = Kem::MlKem768.gen_keypair();
+    let public_key = PublicKey::new(Kem::MlKem768, &keypair.public_key).unwrap();
+    // {1: 7, 3: -83, -1: h'...'}, with a 1184 byte public key
+    assert_eq!(
+        public_key.to_cose_key().unwrap(),
+        [
+            &[0xa3, 0x01, 0x07, 0x03, 0x38, 0x52, 0x20, 0x59, 0x04, 0xa0],
+            &keypair.public_key[..]
+        ]
+        .concat()
+    );
+
+    // {..., -2: h'...'}, with the 64 byte seed
+    assert_eq!(
+        *keypair.to_cose_key(Kem::MlKem768).unwrap(),
+        [
+            &[0xa4, 0x01, 0x07, 0x03, 0x38, 0x52, 0x20, 0x59, 0x04, 0xa0],
+            &keypair.public_key[..],
+            &[0x21, 0x58, 0x40],
+            &keypair.private_key[..]
+        ]
+        .concat()
+    );
+}
+
+#[test]
+fn test_invalid_cose_keys() {
+    let keypair = Kem::X25519HkdfSha256.gen_keypair();
+    let cose_key = keypair.to_cose_key(Kem::X25519HkdfSha256).unwrap();
+
+    let trailing = [&cose_key[..], &[0]].concat();
+    assert_eq!(
+        Keypair::from_cose_key(&trailing),
+        Err(Error::InvalidKeyEncoding)
+    );
+
+    // X448
+    let mut unknown_curve = cose_key.to_vec();
+    unknown_curve[4] = 0x05;
+    assert_eq!(
+        Keypair::from_cose_key(&unknown_curve),
+        Err(Error::InvalidKeyEncoding)
+    );
+
+    // a second kty parameter
+    let duplicate = [&[0xa5][..], &cose_key[1..], &[0x01, 0x01]].concat();
+    assert_eq!(
+        Keypair::from_cose_key(&duplicate),
+        Err(Error::InvalidKeyEncoding)
+    );
+
+    let other_public_key = Kem::X25519HkdfSha256.gen_keypair().public_key;
+    let mut mismatched = cose_key.to_vec();
+    mismatched[8..40].copy_from_slice(&other_public_key);
+    assert_eq!(
+        Keypair::from_cose_key(&mismatched),
+        Err(Error::KeypairMismatch)
+    );
+
+    // an AKP key with a private use algorithm value
+    let akp = hex::decode("a3010703 3a0001003f 2041 00".replace(' ', "")).unwrap();
+    assert_eq!(
+        PublicKey::from_cose_key(&akp),
+        Err(Error::InvalidKeyEncoding)
+    );
+
+    assert_eq!(
+        PublicKey::from_cose_key(b"not cbor"),
+        Err(Error::InvalidKeyEncoding)
+    );
+}
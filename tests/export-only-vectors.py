@@ -0,0 +1,163 @@
+"""Derive export-only test vectors for the one-stage (SHAKE and TurboSHAKE) KDFs.
+
+The published hpke-pq vectors have no export-only (aead_id 0xFFFF) entries. This reruns the
+one-stage key schedule and exporter of draft-ietf-hpke-pq on the shared secret of each of their
+one-stage vectors with aead_id 0xFFFF, after checking that it reproduces the published values.
+It shares no code with hpke-dispatch or hpke.
+
+    python3 tests/export-only-vectors.py tests/test-vectors-pq.json \\
+        > tests/test-vectors-export-only.json
+"""
+
+import hashlib
+import json
+import struct
+import sys
+
+# Keccak-p[1600, 12] and TurboSHAKE, as described in RFC9861
+
+ROUND_CONSTANTS = [
+    0x0000000000000001, 0x0000000000008082, 0x800000000000808A, 0x8000000080008000,
+    0x000000000000808B, 0x0000000080000001, 0x8000000080008081, 0x8000000000008009,
+    0x000000000000008A, 0x0000000000000088, 0x0000000080008009, 0x000000008000000A,
+    0x000000008000808B, 0x800000000000008B, 0x8000000000008089, 0x8000000000008003,
+    0x8000000000008002, 0x8000000000000080, 0x000000000000800A, 0x800000008000000A,
+    0x8000000080008081, 0x8000000000008080, 0x0000000080000001, 0x8000000080008008,
+]
+ROTATIONS = [
+    [0, 36, 3, 41, 18],
+    [1, 44, 10, 45, 2],
+    [62, 6, 43, 15, 61],
+    [28, 55, 25, 21, 56],
+    [27, 20, 39, 8, 14],
+]
+MASK = (1 << 64) - 1
+
+
+def rotate(lane, n):
+    return ((lane << n) | (lane >> (64 - n))) & MASK if n else lane
+
+
+def keccak_p(state, rounds):
+    a = [[state[x + 5 * y] for y in range(5)] for x in range(5)]
+    for constant in ROUND_CONSTANTS[24 - rounds:]:
+        c = [a[x][0] ^ a[x][1] ^ a[x][2] ^ a[x][3] ^ a[x][4] for x in range(5)]
+        d = [c[(x - 1) % 5] ^ rotate(c[(x + 1) % 5], 1) for x in range(5)]
+        a = [[a[x][y] ^ d[x] for y in range(5)] for x in range(5)]
+        b = [[0] * 5 for _ in range(5)]
+        for x in range(5):
+            for y in range(5):
+                b[y][(2 * x + 3 * y) % 5] = rotate(a[x][y], ROTATIONS[x][y])
+        a = [[b[x][y] ^ (~b[(x + 1) % 5][y] & b[(x + 2) % 5][y]) for y in range(5)]
+             for x in range(5)]
+        a[0][0] ^= constant
+    return [a[i % 5][i // 5] for i in range(25)]
+
+
+def turboshake(rate, message, domain, length):
+    padded = bytearray(message) + bytes([domain])
+    padded += bytes(-len(padded) % rate)
+    padded[-1] ^= 0x80
+    state = [0] * 25
+    for offset in range(0, len(padded), rate):
+        for i in range(rate // 8):
+            state[i] ^= struct.unpack_from("<Q", padded, offset + 8 * i)[0]
+        state = keccak_p(state, 12)
+    output = b""
+    while len(output) < length:
+        output += b"".join(struct.pack("<Q", state[i]) for i in range(rate // 8))
+        state = keccak_p(state, 12)
+    return output[:length]
+
+
+# The one-stage key schedule, as described in draft-ietf-hpke-pq
+
+XOFS = {
+    0x0010: lambda message, length: hashlib.shake_128(message).digest(length),
+    0x0011: lambda message, length: hashlib.shake_256(message).digest(length),
+    0x0012: lambda message, length: turboshake(168, message, 0x1F, length),
+    0x0013: lambda message, length: turboshake(136, message, 0x1F, length),
+}
+NH = {0x0010: 32, 0x0011: 64, 0x0012: 32, 0x0013: 64}
+NK = {0x0001: 16, 0x0002: 32, 0x0003: 32, 0xFFFF: 0}
+NN = {0x0001: 12, 0x0002: 12, 0x0003: 12, 0xFFFF: 0}
+
+# X448 is not supported by hpke-dispatch
+SUPPORTED_KEMS = {0x0010, 0x0011, 0x0012, 0x0020, 0x0041, 0x0042, 0x0050, 0x0051, 0x647A}
+
+
+def length_prefixed(value):
+    return struct.pack(">H", len(value)) + value
+
+
+def labeled_derive(kdf_id, suite_id, ikm, label, context, length):
+    labeled_ikm = (ikm + b"HPKE-v1" + suite_id + length_prefixed(label)
+                   + struct.pack(">H", length) + context)
+    return XOFS[kdf_id](labeled_ikm, length)
+
+
+def key_schedule(kem_id, kdf_id, aead_id, shared_secret, info):
+    """base mode only, as the vectors are only derived from base mode vectors"""
+    suite_id = b"HPKE" + struct.pack(">HHH", kem_id, kdf_id, aead_id)
+    secrets = length_prefixed(b"") + length_prefixed(shared_secret)
+    context = b"\x00" + length_prefixed(b"") + length_prefixed(info)
+    nk, nn = NK[aead_id], NN[aead_id]
+    secret = labeled_derive(kdf_id, suite_id, secrets, b"secret", context, nk + nn + NH[kdf_id])
+    return suite_id, secret[:nk], secret[nk:nk + nn], secret[nk + nn:]
+
+
+def export(kdf_id, suite_id, exporter_secret, exporter_context, length):
+    return labeled_derive(kdf_id, suite_id, exporter_secret, b"sec", exporter_context, length)
+
+
+def derive(vector, aead_id):
+    suite_id, key, base_nonce, exporter_secret = key_schedule(
+        vector["kem_id"],
+        vector["kdf_id"],
+        aead_id,
+        bytes.fromhex(vector["shared_secret"]),
+        bytes.fromhex(vector["info"]),
+    )
+    exports = [
+        dict(
+            record,
+            exported_value=export(
+                vector["kdf_id"],
+                suite_id,
+                exporter_secret,
+                bytes.fromhex(record["exporter_context"]),
+                record["L"],
+            ).hex(),
+        )
+        for record in vector["exports"]
+    ]
+    return dict(
+        vector,
+        aead_id=aead_id,
+        suite_id=suite_id.hex(),
+        key=key.hex(),
+        base_nonce=base_nonce.hex(),
+        exporter_secret=exporter_secret.hex(),
+        exports=exports,
+    )
+
+
+def main():
+    derived = []
+    for vector in json.load(open(sys.argv[1])):
+        if vector["mode"] != 0 or vector["kdf_id"] not in XOFS:
+            continue
+
+        published = derive(vector, vector["aead_id"])
+        for field in ["key", "base_nonce", "exporter_secret", "exports"]:
+            assert published[field] == vector[field], (field, vector["kdf_id"])
+
+        if vector["kem_id"] in SUPPORTED_KEMS:
+            derived.append(dict(derive(vector, 0xFFFF), encryptions=[]))
+
+    json.dump(derived, sys.stdout, indent=2)
+    print()
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,154 @@
+#![cfg(all(
+    feature = "jwk",
+    feature = "nistp",
+    feature = "x25519",
+    feature = "mlkem"
+))]
+
+use hpke_dispatch::*;
+use serde_json::json;
+
+fn config(kem: Kem) -> Config {
+    Config {
+        aead: Aead::AesGcm128,
+        kdf: Kdf::Sha256,
+        kem,
+    }
+}
+
+#[test]
+fn test_jwk_round_trips() {
+    for kem in KEM_ALL {
+        let keypair = kem.gen_keypair();
+        let public_key = PublicKey::new(*kem, &keypair.public_key).unwrap();
+        if matches!(kem, Kem::XWing | Kem::MlKem768P256 | Kem::MlKem1024P384) {
+            assert_eq!(
+                keypair.to_jwk(&config(*kem)),
+                Err(Error::UnsupportedKeyEncoding { kem: *kem })
+            );
+            continue;
+        }
+
+        let private_jwk = keypair
+            .to_jwk(&config(*kem))
+            .unwrap_or_else(|e| panic!("problem with {kem:?} to_jwk ({e:?})"));
+        let json = serde_json::to_string(&private_jwk).unwrap();
+        let private_jwk = serde_json::from_str::<Jwk>(&json).unwrap();
+        assert_eq!(
+            Keypair::from_jwk(&private_jwk).unwrap(),
+            (*kem, keypair.clone())
+        );
+        assert_eq!(PublicKey::from_jwk(&private_jwk).unwrap(), public_key);
+
+        let public_jwk = public_key.to_jwk(&config(*kem)).unwrap();
+        assert!(public_jwk.d.is_none() && public_jwk.private.is_none());
+        let json = serde_json::to_string(&public_jwk).unwrap();
+        let public_jwk = serde_json::from_str::<Jwk>(&json).unwrap();
+        assert_eq!(PublicKey::from_jwk(&public_jwk).unwrap(), public_key);
+        assert_eq!(
+            Keypair::from_jwk(&public_jwk),
+            Err(Error::InvalidKeyEncoding)
+        );
+    }
+}
+
+#[test]
+fn test_jwk_rfc8037() {
+    // Bob's key from RFC8037 Appendix A.6
+    let jwk = serde_json::from_value::<Jwk>(json!({
+        "kty": "OKP",
+        "crv": "X25519",
+        "x": "3p7bfXt9wbTTW2HC7OQ1Nz-DQ8hbeGdNrfx-FG-IK08",
+        "d": "XasIfmJKikt54X-Lg4AO5m87sSkmGLb9HC-LJ_-I4Os",
+    }))
+    .unwrap();
+    let (kem, keypair) = Keypair::from_jwk(&jwk).unwrap();
+    assert_eq!(kem, Kem::X25519HkdfSha256);
+    assert_eq!(
+        hex::encode(keypair.public_key),
+        "de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f"
+    );
+    assert_eq!(jwk.config(), None);
+}
+
+#[test]
+fn test_jwk_members() {
+    let keypair = Kem::DhP256HkdfSha256.gen_keypair();
+    let jwk = keypair.to_jwk(&config(Kem::DhP256HkdfSha256)).unwrap();
+    let json = serde_json::to_value(&jwk).unwrap();
+    let members = json.as_object().unwrap();
+    assert_eq!(
+        members.keys().collect::<Vec<_>>(),
+        ["alg", "crv", "d", "kty", "x", "y"]
+    );
+    assert_eq!(json["kty"], "EC");
+    assert_eq!(json["crv"], "P-256");
+    assert_eq!(json["alg"], "HPKE-0");
+    assert_eq!(jwk.config(), Some(config(Kem::DhP256HkdfSha256)));
+    assert!(!format!("{jwk:?}").contains(" d: "));
+
+    let jwk = Kem::MlKem768
+        .gen_keypair()
+        .to_jwk(&config(Kem::MlKem768))
+        .unwrap();
+    let json = serde_json::to_value(&jwk).unwrap();
+    assert_eq!(json["kty"], "AKP");
+    assert_eq!(json["alg"], "ML-KEM-768");
+    assert!(json["pub"].is_string() && json["priv"].is_string());
+    assert_eq!(jwk.config(), None);
+
+    // configs that are not JOSE HPKE algorithms have no alg hint
+    let shake = Config {
+        kdf: Kdf::Shake128,
+        ..config(Kem::X25519HkdfSha256)
+    };
+    let jwk = Kem::X25519HkdfSha256.gen_keypair().to_jwk(&shake).unwrap();
+    assert_eq!(jwk.alg, None);
+}
+
+#[test]
+fn test_invalid_jwks() {
+    let keypair = Kem::X25519HkdfSha256.gen_keypair();
+    let jwk = keypair.to_jwk(&config(Kem::X25519HkdfSha256)).unwrap();
+    assert_eq!(jwk.alg.as_deref(), Some("HPKE-3"));
+
+    let wrong_alg = Jwk {
+        alg: Some("HPKE-0".into()),
+        ..jwk.clone()
+    };
+    assert_eq!(
+        PublicKey::from_jwk(&wrong_alg),
+        Err(Error::InvalidKeyEncoding)
+    );
+
+    let unknown_curve = Jwk {
+        crv: Some("X448".into()),
+        ..jwk.clone()
+    };
+    assert_eq!(
+        PublicKey::from_jwk(&unknown_curve),
+        Err(Error::InvalidKeyEncoding)
+    );
+
+    let mismatched = Jwk {
+        x: Some(Kem::X25519HkdfSha256.gen_keypair().public_key),
+        ..jwk.clone()
+    };
+    assert_eq!(Keypair::from_jwk(&mismatched), Err(Error::KeypairMismatch));
+
+    assert!(serde_json::from_value::<Jwk>(json!({
+        "kty": "OKP",
+        "crv": "X25519",
+        "x": "not base64url!",
+    }))
+    .is_err());
+
+    let public_key = PublicKey::new(Kem::X25519HkdfSha256, &keypair.public_key).unwrap();
+    assert_eq!(
+        public_key.to_jwk(&config(Kem::DhP256HkdfSha256)),
+        Err(Error::KemMismatch {
+            expected: Kem::DhP256HkdfSha256,
+            found: Kem::X25519HkdfSha256
+        })
+    );
+}
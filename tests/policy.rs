@@ -0,0 +1,225 @@
+use hpke_dispatch::*;
+
+mod common;
+use common::{all_configs, encrypting_configs};
+
+const PLAINTEXT: &[u8] = b"plaintext test message";
+const APP_INFO: &[u8] = b"application info";
+const AAD: &[u8] = b"associated data";
+
+#[test]
+fn test_default_policy_allows_everything() {
+    let policy = Policy::default();
+    for config in all_configs() {
+        assert_eq!(policy.check_seal(&config), Ok(()));
+        assert_eq!(policy.check_open(&config), Ok(()));
+    }
+}
+
+#[test]
+fn test_algorithm_classification() {
+    for kem in KEM_ALL {
+        assert!([128, 192, 256].contains(&kem.security_level()));
+        // only the Diffie-Hellman KEMs, which support auth, are classical-only
+        assert_eq!(
+            kem.quantum_resistance() == QuantumResistance::Classical,
+            kem.supports_auth(),
+            "{kem:?}"
+        );
+    }
+    for kdf in KDF_ALL {
+        assert!([128, 192, 256].contains(&kdf.security_level()));
+        assert_eq!(kdf.quantum_resistance(), QuantumResistance::PostQuantum);
+    }
+    for aead in AEAD_ALL {
+        assert_eq!(aead.security_level().is_none(), *aead == Aead::ExportOnly);
+        assert_eq!(aead.quantum_resistance(), QuantumResistance::PostQuantum);
+    }
+
+    assert!(!QuantumResistance::Classical.is_quantum_resistant());
+    assert!(QuantumResistance::PostQuantum.is_quantum_resistant());
+    assert!(QuantumResistance::Hybrid.is_quantum_resistant());
+
+    #[cfg(feature = "nistp")]
+    {
+        assert_eq!(Kem::DhP384HkdfSha384.security_level(), 192);
+        assert!(Kem::DhP256HkdfSha256.is_fips_approved());
+    }
+    #[cfg(feature = "x25519")]
+    assert!(!Kem::X25519HkdfSha256.is_fips_approved());
+    #[cfg(feature = "mlkem")]
+    {
+        assert_eq!(
+            Kem::MlKem768.quantum_resistance(),
+            QuantumResistance::PostQuantum
+        );
+        assert!(Kem::MlKem1024.is_fips_approved());
+    }
+    #[cfg(all(feature = "mlkem", feature = "x25519"))]
+    assert_eq!(Kem::XWing.quantum_resistance(), QuantumResistance::Hybrid);
+    #[cfg(feature = "hkdfsha2")]
+    assert!(Kdf::Sha256.is_fips_approved());
+    #[cfg(feature = "shake")]
+    assert!(!Kdf::Shake256.is_fips_approved());
+    #[cfg(feature = "aes")]
+    assert!(Aead::AesGcm128.is_fips_approved());
+    #[cfg(feature = "chacha")]
+    assert!(!Aead::ChaCha20Poly1305.is_fips_approved());
+}
+
+#[test]
+fn test_require_quantum_resistance() {
+    let policy = Policy {
+        require_quantum_resistance: true,
+        ..Policy::default()
+    };
+
+    for config in encrypting_configs() {
+        assert_eq!(policy.check_open(&config), Ok(()));
+        if config.kem.quantum_resistance().is_quantum_resistant() {
+            assert_eq!(policy.check_seal(&config), Ok(()));
+            continue;
+        }
+
+        let error = policy.check_seal(&config).unwrap_err();
+        assert_eq!(
+            error,
+            Error::PolicyViolation(PolicyViolation::NotQuantumResistant(config.kem))
+        );
+        assert_eq!(
+            error.to_string(),
+            format!("policy violation: {} is not quantum resistant", config.kem)
+        );
+
+        // a message sealed before the policy was adopted can still be opened
+        let keypair = config.kem.gen_keypair();
+        let (encapped_key, ciphertext) = config
+            .base_mode_seal(&keypair.public_key, APP_INFO, PLAINTEXT, AAD)
+            .unwrap()
+            .into_parts();
+        let received =
+            Config::try_from_ids(config.aead as u16, config.kdf as u16, config.kem as u16).unwrap();
+        policy.check_open(&received).unwrap();
+        assert_eq!(
+            received
+                .base_mode_open(
+                    &keypair.private_key,
+                    &encapped_key,
+                    APP_INFO,
+                    &ciphertext,
+                    AAD
+                )
+                .unwrap(),
+            PLAINTEXT
+        );
+    }
+}
+
+#[test]
+fn test_allowlists() {
+    let config = Config {
+        aead: AEAD_ALL[0],
+        kdf: KDF_ALL[0],
+        kem: KEM_ALL[0],
+    };
+    let allowed = Policy {
+        aeads: Some(vec![config.aead]),
+        kdfs: Some(vec![config.kdf]),
+        kems: Some(vec![config.kem]),
+        ..Policy::default()
+    };
+    assert_eq!(allowed.check_seal(&config), Ok(()));
+
+    let no_aead = Policy {
+        aeads: Some(vec![]),
+        ..allowed.clone()
+    };
+    assert_eq!(
+        no_aead.check_open(&config),
+        Err(Error::PolicyViolation(PolicyViolation::AeadNotAllowed(
+            config.aead
+        )))
+    );
+    let no_kdf = Policy {
+        kdfs: Some(vec![]),
+        ..allowed.clone()
+    };
+    assert_eq!(
+        no_kdf.check_open(&config),
+        Err(Error::PolicyViolation(PolicyViolation::KdfNotAllowed(
+            config.kdf
+        )))
+    );
+    let no_kem = Policy {
+        kems: Some(vec![]),
+        ..allowed
+    };
+    assert_eq!(
+        no_kem.check_seal(&config),
+        Err(Error::PolicyViolation(PolicyViolation::KemNotAllowed(
+            config.kem
+        )))
+    );
+}
+
+#[test]
+fn test_min_security_level() {
+    let policy = Policy {
+        min_security_level: 192,
+        ..Policy::default()
+    };
+    for config in all_configs() {
+        let levels = [
+            (AlgorithmKind::Aead, config.aead.security_level()),
+            (AlgorithmKind::Kdf, Some(config.kdf.security_level())),
+            (AlgorithmKind::Kem, Some(config.kem.security_level())),
+        ];
+        let expected = match levels
+            .into_iter()
+            .find_map(|(kind, level)| Some((kind, level?)).filter(|(_, level)| *level < 192))
+        {
+            Some((kind, level)) => Err(Error::PolicyViolation(
+                PolicyViolation::SecurityLevelTooLow {
+                    kind,
+                    level,
+                    minimum: 192,
+                },
+            )),
+            None => Ok(()),
+        };
+        assert_eq!(policy.check_open(&config), expected, "{config:?}");
+        assert_eq!(policy.check_seal(&config), expected, "{config:?}");
+    }
+
+    #[cfg(all(feature = "aes", feature = "hkdfsha2", feature = "nistp"))]
+    {
+        let config = Config {
+            aead: Aead::AesGcm256,
+            kdf: Kdf::Sha384,
+            kem: Kem::DhP256HkdfSha256,
+        };
+        assert_eq!(
+            policy.check_open(&config).unwrap_err().to_string(),
+            "policy violation: kem security level of 128 bits is below the minimum of 192"
+        );
+    }
+
+    // the export-only aead has no security level, so it passes any minimum
+    for config in all_configs().filter(|config| config.aead == Aead::ExportOnly) {
+        let policy = Policy {
+            min_security_level: config.kdf.security_level().min(config.kem.security_level()),
+            ..Policy::default()
+        };
+        assert_eq!(policy.check_open(&config), Ok(()), "{config:?}");
+        assert_eq!(policy.check_seal(&config), Ok(()), "{config:?}");
+
+        #[cfg(feature = "aes")]
+        if policy.min_security_level > 128 {
+            let config = Config {
+                aead: Aead::AesGcm128,
+                ..config
+            };
+            assert!(policy.check_seal(&config).is_err(), "{config:?}");
+        }
+    }
+}
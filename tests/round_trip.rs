@@ -1,8 +1,13 @@
 use hpke_dispatch::*;
 
+mod common;
+use common::{encrypting_aeads, encrypting_configs};
+
 const AAD: &[u8] = b"associated data";
 const PLAINTEXT: &[u8] = b"plaintext test message";
 const APP_INFO: &[u8] = b"application info";
+const PSK: &[u8] = b"a pre-shared key of at least 32 bytes";
+const PSK_ID: &[u8] = b"pre-shared key id";
 
 fn test_round_trip(config: Config) {
     let (private_key, public_key) = config.kem.gen_keypair().into_parts();
@@ -22,13 +27,1038 @@ fn test_round_trip(config: Config) {
     );
 }
 
+fn test_psk_round_trip(config: Config) {
+    let (private_key, public_key) = config.kem.gen_keypair().into_parts();
+
+    let (encapped_key, ciphertext) = config
+        .psk_mode_seal(&public_key, PSK, PSK_ID, APP_INFO, PLAINTEXT, AAD)
+        .unwrap_or_else(|e| panic!("problem with {config:?} psk_mode_seal ({e:?})"))
+        .into_parts();
+
+    let plaintext = config
+        .psk_mode_open(
+            &private_key,
+            &encapped_key,
+            PSK,
+            PSK_ID,
+            APP_INFO,
+            &ciphertext,
+            AAD,
+        )
+        .unwrap_or_else(|e| panic!("problem with {config:?} psk_mode_open ({e:?})"));
+
+    assert_eq!(
+        plaintext, PLAINTEXT,
+        "psk round trip plaintext did not match for {config:?}"
+    );
+
+    assert!(
+        config
+            .psk_mode_open(
+                &private_key,
+                &encapped_key,
+                b"a different pre-shared key of at least 32 bytes",
+                PSK_ID,
+                APP_INFO,
+                &ciphertext,
+                AAD,
+            )
+            .is_err(),
+        "opened with the wrong psk for {config:?}"
+    );
+}
+
+fn test_auth_round_trip(config: Config) {
+    let (private_key, public_key) = config.kem.gen_keypair().into_parts();
+    let (sender_private_key, sender_public_key) = config.kem.gen_keypair().into_parts();
+
+    let (encapped_key, ciphertext) = config
+        .auth_mode_seal(
+            &public_key,
+            &sender_private_key,
+            &sender_public_key,
+            APP_INFO,
+            PLAINTEXT,
+            AAD,
+        )
+        .unwrap_or_else(|e| panic!("problem with {config:?} auth_mode_seal ({e:?})"))
+        .into_parts();
+
+    let plaintext = config
+        .auth_mode_open(
+            &private_key,
+            &sender_public_key,
+            &encapped_key,
+            APP_INFO,
+            &ciphertext,
+            AAD,
+        )
+        .unwrap_or_else(|e| panic!("problem with {config:?} auth_mode_open ({e:?})"));
+
+    assert_eq!(
+        plaintext, PLAINTEXT,
+        "auth round trip plaintext did not match for {config:?}"
+    );
+
+    let other_public_key = config.kem.gen_keypair().public_key;
+    assert!(
+        config
+            .auth_mode_open(
+                &private_key,
+                &other_public_key,
+                &encapped_key,
+                APP_INFO,
+                &ciphertext,
+                AAD,
+            )
+            .is_err(),
+        "opened with the wrong sender public key for {config:?}"
+    );
+}
+
+fn test_auth_psk_round_trip(config: Config) {
+    let (private_key, public_key) = config.kem.gen_keypair().into_parts();
+    let (sender_private_key, sender_public_key) = config.kem.gen_keypair().into_parts();
+
+    let (encapped_key, ciphertext) = config
+        .auth_psk_mode_seal(
+            &public_key,
+            &sender_private_key,
+            &sender_public_key,
+            PSK,
+            PSK_ID,
+            APP_INFO,
+            PLAINTEXT,
+            AAD,
+        )
+        .unwrap_or_else(|e| panic!("problem with {config:?} auth_psk_mode_seal ({e:?})"))
+        .into_parts();
+
+    let plaintext = config
+        .auth_psk_mode_open(
+            &private_key,
+            &sender_public_key,
+            &encapped_key,
+            PSK,
+            PSK_ID,
+            APP_INFO,
+            &ciphertext,
+            AAD,
+        )
+        .unwrap_or_else(|e| panic!("problem with {config:?} auth_psk_mode_open ({e:?})"));
+
+    assert_eq!(
+        plaintext, PLAINTEXT,
+        "auth psk round trip plaintext did not match for {config:?}"
+    );
+}
+
+fn test_stateful_round_trip(config: Config) {
+    let (private_key, public_key) = config.kem.gen_keypair().into_parts();
+
+    let (encapped_key, mut sender) = config
+        .setup_sender(&public_key, APP_INFO)
+        .unwrap_or_else(|e| panic!("problem with {config:?} setup_sender ({e:?})"));
+    let mut receiver = config
+        .setup_receiver(&private_key, &encapped_key, APP_INFO)
+        .unwrap_or_else(|e| panic!("problem with {config:?} setup_receiver ({e:?})"));
+
+    let ciphertexts = (0..3)
+        .map(|_| sender.seal(PLAINTEXT, AAD).unwrap())
+        .collect::<Vec<_>>();
+    assert_ne!(ciphertexts[0], ciphertexts[1]);
+
+    assert!(
+        receiver.open(&ciphertexts[1], AAD).is_err(),
+        "opened out of order for {config:?}"
+    );
+
+    for (seq, ciphertext) in ciphertexts.iter().enumerate().rev() {
+        let plaintext = receiver
+            .open_at(seq as u64, ciphertext, AAD)
+            .unwrap_or_else(|e| panic!("problem with {config:?} open_at ({e:?})"));
+        assert_eq!(
+            plaintext, PLAINTEXT,
+            "random access plaintext did not match for {config:?}"
+        );
+    }
+    assert!(receiver.open_at(0, &ciphertexts[1], AAD).is_err());
+    assert_eq!(receiver.seq(), Some(0));
+
+    for ciphertext in &ciphertexts {
+        let plaintext = receiver
+            .open(ciphertext, AAD)
+            .unwrap_or_else(|e| panic!("problem with {config:?} open ({e:?})"));
+        assert_eq!(
+            plaintext, PLAINTEXT,
+            "stateful round trip plaintext did not match for {config:?}"
+        );
+    }
+    assert_eq!(receiver.seq(), Some(3));
+
+    let (seq, ciphertext) = sender.seal_with_seq(PLAINTEXT, AAD).unwrap();
+    assert_eq!(seq, 3);
+    assert_eq!(sender.seq(), Some(4));
+    assert_eq!(receiver.open_at(seq, &ciphertext, AAD).unwrap(), PLAINTEXT);
+}
+
+fn test_context_resumption(config: Config) {
+    let (private_key, public_key) = config.kem.gen_keypair().into_parts();
+
+    let (encapped_key, mut sender) = config.setup_sender(&public_key, APP_INFO).unwrap();
+    let mut receiver = config
+        .setup_receiver(&private_key, &encapped_key, APP_INFO)
+        .unwrap();
+    let ciphertext = sender.seal(PLAINTEXT, AAD).unwrap();
+    receiver.open(&ciphertext, AAD).unwrap();
+    let exported = sender.export(b"exporter context", 32).unwrap();
+
+    let sender_state = sender.save_state();
+    let receiver_state = receiver.save_state();
+    drop((sender, receiver));
+
+    let mut sender = config
+        .resume_sender(&sender_state)
+        .unwrap_or_else(|e| panic!("problem with {config:?} resume_sender ({e:?})"));
+    let mut receiver = config
+        .resume_receiver(&receiver_state)
+        .unwrap_or_else(|e| panic!("problem with {config:?} resume_receiver ({e:?})"));
+    assert_eq!(sender.seq(), Some(1));
+    assert_eq!(receiver.seq(), Some(1));
+
+    let ciphertext = sender.seal(PLAINTEXT, AAD).unwrap();
+    assert_eq!(
+        receiver.open(&ciphertext, AAD).unwrap(),
+        PLAINTEXT,
+        "resumed plaintext did not match for {config:?}"
+    );
+    assert_eq!(sender.export(b"exporter context", 32).unwrap(), exported);
+    assert_eq!(receiver.export(b"exporter context", 32).unwrap(), exported);
+
+    assert_eq!(
+        config.resume_sender(&receiver_state).unwrap_err(),
+        Error::InvalidContextState
+    );
+    assert_eq!(
+        config
+            .resume_receiver(&receiver_state[..receiver_state.len() - 1])
+            .unwrap_err(),
+        Error::InvalidContextState
+    );
+    assert_eq!(
+        config
+            .resume_receiver(&[&receiver_state[..], &[0]].concat())
+            .unwrap_err(),
+        Error::InvalidContextState
+    );
+}
+
+fn test_stateful_key_schedule(config: Config) {
+    let (private_key, public_key) = config.kem.gen_keypair().into_parts();
+
+    // the first ciphertext of a context is a single-shot base mode ciphertext
+    let (encapped_key, mut sender) = config.setup_sender(&public_key, APP_INFO).unwrap();
+    let ciphertext = sender.seal(PLAINTEXT, AAD).unwrap();
+    let plaintext = config
+        .base_mode_open(&private_key, &encapped_key, APP_INFO, &ciphertext, AAD)
+        .unwrap_or_else(|e| panic!("problem with {config:?} base_mode_open ({e:?})"));
+    assert_eq!(plaintext, PLAINTEXT);
+
+    let (encapped_key, ciphertext) = config
+        .base_mode_seal(&public_key, APP_INFO, PLAINTEXT, AAD)
+        .unwrap()
+        .into_parts();
+    let mut receiver = config
+        .setup_receiver(&private_key, &encapped_key, APP_INFO)
+        .unwrap();
+    let plaintext = receiver
+        .open(&ciphertext, AAD)
+        .unwrap_or_else(|e| panic!("problem with {config:?} open ({e:?})"));
+    assert_eq!(plaintext, PLAINTEXT);
+}
+
+fn test_export_round_trip(config: Config) {
+    let (private_key, public_key) = config.kem.gen_keypair().into_parts();
+
+    let (encapped_key, secret) = config
+        .sender_export(&public_key, APP_INFO, b"exporter context", 32)
+        .unwrap_or_else(|e| panic!("problem with {config:?} sender_export ({e:?})"))
+        .into_parts();
+    assert_eq!(secret.len(), 32);
+
+    let received_secret = config
+        .receiver_export(
+            &private_key,
+            &encapped_key,
+            APP_INFO,
+            b"exporter context",
+            32,
+        )
+        .unwrap_or_else(|e| panic!("problem with {config:?} receiver_export ({e:?})"));
+    assert_eq!(
+        secret, received_secret,
+        "exported secrets did not match for {config:?}"
+    );
+
+    let other_secret = config
+        .receiver_export(
+            &private_key,
+            &encapped_key,
+            APP_INFO,
+            b"other exporter context",
+            32,
+        )
+        .unwrap();
+    assert_ne!(secret, other_secret);
+}
+
 #[test]
 fn test_all_round_trips() {
-    for ((aead, kdf), kem) in AEAD_ALL.iter().zip(KDF_ALL).zip(KEM_ALL) {
-        test_round_trip(Config {
-            aead: *aead,
-            kdf: *kdf,
+    for config in encrypting_configs() {
+        test_round_trip(config);
+    }
+}
+
+#[test]
+fn test_all_psk_round_trips() {
+    for config in encrypting_configs() {
+        test_psk_round_trip(config);
+    }
+}
+
+#[test]
+fn test_all_stateful_round_trips() {
+    for config in encrypting_configs() {
+        test_stateful_round_trip(config);
+    }
+}
+
+#[test]
+fn test_all_context_resumptions() {
+    for config in encrypting_configs() {
+        test_context_resumption(config);
+    }
+}
+
+#[test]
+fn test_context_resumption_config_mismatch() {
+    let config = Config {
+        aead: AEAD_ALL[0],
+        kdf: KDF_ALL[0],
+        kem: KEM_ALL[0],
+    };
+    let other_config = Config {
+        aead: AEAD_ALL[1],
+        ..config
+    };
+    let public_key = config.kem.gen_keypair().public_key;
+    let (_, sender) = config.setup_sender(&public_key, APP_INFO).unwrap();
+
+    assert_eq!(
+        other_config
+            .resume_sender(&sender.save_state())
+            .unwrap_err(),
+        Error::ContextConfigMismatch {
+            expected: other_config,
+            found: config,
+        }
+    );
+}
+
+#[test]
+fn test_all_stateful_key_schedules() {
+    for config in encrypting_configs() {
+        test_stateful_key_schedule(config);
+    }
+}
+
+#[test]
+fn test_all_export_round_trips() {
+    for config in encrypting_configs() {
+        test_export_round_trip(config);
+    }
+}
+
+#[test]
+fn test_all_auth_round_trips() {
+    let aeads = encrypting_aeads();
+    for (i, kem) in KEM_ALL.iter().enumerate() {
+        let config = Config {
+            aead: aeads[i % aeads.len()],
+            kdf: KDF_ALL[i % KDF_ALL.len()],
             kem: *kem,
+        };
+
+        if kem.supports_auth() {
+            test_auth_round_trip(config);
+            test_auth_psk_round_trip(config);
+        } else {
+            let (private_key, public_key) = kem.gen_keypair().into_parts();
+            assert_eq!(
+                config.auth_mode_seal(
+                    &public_key,
+                    &private_key,
+                    &public_key,
+                    APP_INFO,
+                    PLAINTEXT,
+                    AAD
+                ),
+                Err(Error::UnsupportedMode {
+                    kem: *kem,
+                    mode: Mode::Auth
+                })
+            );
+            assert_eq!(
+                config.auth_psk_mode_open(
+                    &private_key,
+                    &public_key,
+                    &public_key,
+                    PSK,
+                    PSK_ID,
+                    APP_INFO,
+                    PLAINTEXT,
+                    AAD
+                ),
+                Err(Error::UnsupportedMode {
+                    kem: *kem,
+                    mode: Mode::AuthPsk
+                })
+            );
+        }
+    }
+}
+
+fn test_open_errors(config: Config) {
+    let (private_key, public_key) = config.kem.gen_keypair().into_parts();
+    let (encapped_key, mut ciphertext) = config
+        .base_mode_seal(&public_key, APP_INFO, PLAINTEXT, AAD)
+        .unwrap()
+        .into_parts();
+
+    assert_eq!(
+        config.base_mode_seal(&public_key[1..], APP_INFO, PLAINTEXT, AAD),
+        Err(Error::InvalidPublicKey { kem: config.kem })
+    );
+    assert_eq!(
+        Error::InvalidPublicKey { kem: config.kem }.to_string(),
+        format!("invalid {} public key", config.kem)
+    );
+    assert_eq!(
+        config.base_mode_open(&private_key[1..], &encapped_key, APP_INFO, &ciphertext, AAD),
+        Err(Error::InvalidPrivateKey { kem: config.kem })
+    );
+    assert_eq!(
+        config.base_mode_open(&private_key, &encapped_key[1..], APP_INFO, &ciphertext, AAD),
+        Err(Error::InvalidEncappedKey { kem: config.kem })
+    );
+
+    ciphertext[0] ^= 1;
+    assert_eq!(
+        config.base_mode_open(&private_key, &encapped_key, APP_INFO, &ciphertext, AAD),
+        Err(Error::DecryptionFailed { aead: config.aead })
+    );
+    let mut receiver = config
+        .setup_receiver(&private_key, &encapped_key, APP_INFO)
+        .unwrap();
+    assert_eq!(
+        receiver.open(&ciphertext, AAD),
+        Err(Error::DecryptionFailed { aead: config.aead })
+    );
+}
+
+#[test]
+fn test_all_open_errors() {
+    for aead in encrypting_aeads() {
+        for kem in KEM_ALL {
+            test_open_errors(Config {
+                aead,
+                kdf: KDF_ALL[0],
+                kem: *kem,
+            });
+        }
+    }
+}
+
+#[test]
+fn test_unsupported_algorithm() {
+    let error = Config::try_from_ids(0xFFFE, KDF_ALL[0] as u16, KEM_ALL[0] as u16).unwrap_err();
+    let Error::UnsupportedAlgorithm(lookup) = &error else {
+        panic!("unexpected error {error:?}");
+    };
+    assert_eq!(lookup.kind(), AlgorithmKind::Aead);
+    assert_eq!(lookup.value(), &LookupValue::Id(0xFFFE));
+    assert_eq!(lookup.disabled_feature(), None);
+    assert_eq!(lookup.to_string(), "aead id 0xfffe not recognized");
+    assert_eq!(
+        error.to_string(),
+        "unsupported algorithm: aead id 0xfffe not recognized"
+    );
+    assert!(std::error::Error::source(&error).is_some());
+
+    // X448 is registered with IANA, but is not supported by this crate
+    let error = Config::try_from_ids(AEAD_ALL[0] as u16, KDF_ALL[0] as u16, 0x0021).unwrap_err();
+    assert!(
+        matches!(&error, Error::UnsupportedAlgorithm(lookup) if lookup.disabled_feature().is_none())
+    );
+
+    let lookup = "mlkem512".parse::<Kem>().unwrap_err();
+    assert_eq!(lookup.kind(), AlgorithmKind::Kem);
+    assert_eq!(lookup.value(), &LookupValue::Name("mlkem512".into()));
+    assert_eq!(lookup.to_string(), "kem name \"mlkem512\" not recognized");
+
+    #[cfg(not(feature = "mlkem"))]
+    {
+        let error =
+            Config::try_from_ids(AEAD_ALL[0] as u16, KDF_ALL[0] as u16, 0x0041).unwrap_err();
+        let Error::UnsupportedAlgorithm(lookup) = error else {
+            panic!("unexpected error {error:?}");
+        };
+        assert_eq!(lookup.disabled_feature(), Some("mlkem"));
+        assert_eq!(
+            lookup.to_string(),
+            "kem id 0x0041 requires the \"mlkem\" feature"
+        );
+    }
+}
+
+#[test]
+fn test_suite_id() {
+    for aead in AEAD_ALL {
+        assert_eq!(Aead::try_from(u16::from(*aead)), Ok(*aead));
+        for kdf in KDF_ALL {
+            assert_eq!(Kdf::try_from(u16::from(*kdf)), Ok(*kdf));
+            for kem in KEM_ALL {
+                let config = Config {
+                    aead: *aead,
+                    kdf: *kdf,
+                    kem: *kem,
+                };
+                let suite_id = config.suite_id();
+                assert_eq!(&suite_id[..4], b"HPKE");
+                assert_eq!(Config::from_suite_id(&suite_id), Ok(config));
+                assert_eq!(
+                    Config::try_from_ids(u16::from(*aead), u16::from(*kdf), u16::from(*kem)),
+                    Ok(config)
+                );
+            }
+        }
+    }
+    for kem in KEM_ALL {
+        assert_eq!(Kem::try_from(u16::from(*kem)), Ok(*kem));
+    }
+
+    // RFC9180 appendix A.1
+    #[cfg(all(feature = "aes", feature = "hkdfsha2", feature = "x25519"))]
+    {
+        let config = Config {
+            aead: Aead::AesGcm128,
+            kdf: Kdf::Sha256,
+            kem: Kem::X25519HkdfSha256,
+        };
+        assert_eq!(hex::encode(config.suite_id()), "48504b45002000010001");
+        assert_eq!(u16::from(Kem::X25519HkdfSha256), 0x0020);
+    }
+
+    let suite_id = Config {
+        aead: AEAD_ALL[0],
+        kdf: KDF_ALL[0],
+        kem: KEM_ALL[0],
+    }
+    .suite_id();
+    assert_eq!(
+        Config::from_suite_id(&suite_id[..9]),
+        Err(Error::InvalidSuiteId)
+    );
+    assert_eq!(
+        Config::from_suite_id(&[&suite_id[..], &[0]].concat()),
+        Err(Error::InvalidSuiteId)
+    );
+    assert_eq!(
+        Config::from_suite_id(&[b"KEM\0", &suite_id[4..]].concat()),
+        Err(Error::InvalidSuiteId)
+    );
+
+    let mut unsupported = suite_id;
+    unsupported[4..6].copy_from_slice(&0x0021_u16.to_be_bytes());
+    let error = Config::from_suite_id(&unsupported).unwrap_err();
+    let Error::UnsupportedAlgorithm(lookup) = &error else {
+        panic!("unexpected error {error:?}");
+    };
+    assert_eq!(lookup.kind(), AlgorithmKind::Kem);
+    assert_eq!(lookup.value(), &LookupValue::Id(0x0021));
+}
+
+#[test]
+fn test_negotiate() {
+    let configs = encrypting_aeads()
+        .into_iter()
+        .flat_map(|aead| {
+            KEM_ALL.iter().map(move |kem| Config {
+                aead,
+                kdf: KDF_ALL[0],
+                kem: *kem,
+            })
+        })
+        .collect::<Vec<_>>();
+    let ids = |config: &Config| (config.aead as u16, config.kdf as u16, config.kem as u16);
+
+    // our first preference that the peer also advertised wins, whatever the peer's order
+    let preferences = &configs[..];
+    let mut advertised = configs[configs.len() / 2..].to_vec();
+    advertised.reverse();
+    assert_eq!(
+        Config::negotiate(preferences, &advertised),
+        Some(configs[configs.len() / 2])
+    );
+    assert_eq!(Config::negotiate(preferences, &[]), None);
+    assert_eq!(Config::negotiate(&[], &advertised), None);
+
+    // unsupported ids are skipped rather than failing the whole list
+    let unsupported = [
+        (0xFFFE, KDF_ALL[0] as u16, KEM_ALL[0] as u16),
+        (AEAD_ALL[0] as u16, 0xFFFE, KEM_ALL[0] as u16),
+        (AEAD_ALL[0] as u16, KDF_ALL[0] as u16, 0x0021),
+    ];
+    let advertised_ids = unsupported
+        .into_iter()
+        .chain(advertised.iter().map(ids))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        Config::negotiate_ids(preferences, &advertised_ids),
+        Some(configs[configs.len() / 2])
+    );
+    assert_eq!(Config::negotiate_ids(preferences, &unsupported), None);
+}
+
+#[test]
+fn test_derive_keypair() {
+    for kem in KEM_ALL {
+        let ikm = [0x42; 66];
+        let keypair = kem.derive_keypair(&ikm).unwrap();
+        assert_eq!(keypair, derive_keypair(*kem, &ikm).unwrap());
+        assert_ne!(keypair, kem.derive_keypair(&[0x43; 66]).unwrap());
+
+        // RFC9180 only recommends that ikm be as long as the private key
+        assert!(kem.derive_keypair(&ikm[..1]).is_ok());
+
+        let long_ikm = vec![0x42; 65536];
+        if kem.quantum_resistance().is_quantum_resistant() {
+            assert_eq!(
+                kem.derive_keypair(&long_ikm),
+                Err(Error::InvalidIkm { kem: *kem })
+            );
+        } else {
+            assert!(kem.derive_keypair(&long_ikm).is_ok());
+        }
+    }
+}
+
+#[test]
+fn test_keypair_validation() {
+    for kem in KEM_ALL {
+        let keypair = kem.gen_keypair();
+        assert_eq!(keypair.validate(*kem), Ok(()));
+        assert_eq!(
+            kem.public_key_from_private(&keypair.private_key).unwrap(),
+            keypair.public_key
+        );
+
+        let other_keypair = kem.gen_keypair();
+        let mismatched = Keypair {
+            public_key: other_keypair.public_key,
+            private_key: keypair.private_key.clone(),
+        };
+        assert_eq!(mismatched.validate(*kem), Err(Error::KeypairMismatch));
+
+        let truncated = Keypair {
+            public_key: keypair.public_key.clone(),
+            private_key: keypair.private_key[1..].to_vec().into(),
+        };
+        assert_eq!(
+            truncated.validate(*kem),
+            Err(Error::InvalidPrivateKey { kem: *kem })
+        );
+        assert_ne!(truncated, keypair);
+        assert_eq!(keypair.clone(), keypair);
+        assert!(kem.public_key_from_private(&[]).is_err());
+    }
+}
+
+#[test]
+fn test_algorithm_sizes() {
+    for kem in KEM_ALL {
+        let config = Config {
+            aead: encrypting_aeads()[0],
+            kdf: KDF_ALL[0],
+            kem: *kem,
+        };
+        let keypair = kem.gen_keypair();
+        assert_eq!(keypair.public_key.len(), kem.n_pk(), "{kem:?}");
+        assert_eq!(keypair.private_key.len(), kem.n_sk(), "{kem:?}");
+
+        let (encapped_key, ciphertext) = config
+            .base_mode_seal(&keypair.public_key, APP_INFO, PLAINTEXT, AAD)
+            .unwrap()
+            .into_parts();
+        assert_eq!(encapped_key.len(), kem.n_enc(), "{kem:?}");
+        assert_eq!(
+            Some(ciphertext.len()),
+            config.ciphertext_len(PLAINTEXT.len())
+        );
+    }
+
+    for aead in encrypting_aeads() {
+        let config = Config {
+            aead,
+            kdf: KDF_ALL[0],
+            kem: KEM_ALL[0],
+        };
+        assert_eq!(config.ciphertext_len(0), Some(aead.n_t()));
+        assert_eq!(config.ciphertext_len(usize::MAX), None);
+    }
+
+    let export_only = Config {
+        aead: Aead::ExportOnly,
+        kdf: KDF_ALL[0],
+        kem: KEM_ALL[0],
+    };
+    assert_eq!(export_only.ciphertext_len(PLAINTEXT.len()), None);
+    assert_eq!(
+        (
+            Aead::ExportOnly.n_k(),
+            Aead::ExportOnly.n_n(),
+            Aead::ExportOnly.n_t()
+        ),
+        (0, 0, 0)
+    );
+
+    // values from the tables in RFC9180§7
+    #[cfg(feature = "x25519")]
+    {
+        let kem = Kem::X25519HkdfSha256;
+        assert_eq!(
+            (kem.n_secret(), kem.n_enc(), kem.n_pk(), kem.n_sk()),
+            (32, 32, 32, 32)
+        );
+    }
+    #[cfg(feature = "nistp")]
+    {
+        let kem = Kem::DhP256HkdfSha256;
+        assert_eq!(
+            (kem.n_secret(), kem.n_enc(), kem.n_pk(), kem.n_sk()),
+            (32, 65, 65, 32)
+        );
+        let kem = Kem::DhP521HkdfSha512;
+        assert_eq!(
+            (kem.n_secret(), kem.n_enc(), kem.n_pk(), kem.n_sk()),
+            (64, 133, 133, 66)
+        );
+    }
+    #[cfg(feature = "hkdfsha2")]
+    assert_eq!(
+        (Kdf::Sha256.n_h(), Kdf::Sha384.n_h(), Kdf::Sha512.n_h()),
+        (32, 48, 64)
+    );
+    #[cfg(feature = "aes")]
+    {
+        let aead = Aead::AesGcm128;
+        assert_eq!((aead.n_k(), aead.n_n(), aead.n_t()), (16, 12, 16));
+        let aead = Aead::AesGcm256;
+        assert_eq!((aead.n_k(), aead.n_n(), aead.n_t()), (32, 12, 16));
+    }
+    #[cfg(feature = "chacha")]
+    {
+        let aead = Aead::ChaCha20Poly1305;
+        assert_eq!((aead.n_k(), aead.n_n(), aead.n_t()), (32, 12, 16));
+    }
+}
+
+#[test]
+fn test_keypair_secrets() {
+    let config = Config {
+        aead: encrypting_aeads()[0],
+        kdf: KDF_ALL[0],
+        kem: KEM_ALL[0],
+    };
+    let keypair = config.kem.gen_keypair();
+    let debug = format!("{keypair:?}");
+    assert!(debug.contains(&format!("{:?}", keypair.public_key)));
+    assert!(!debug.contains("private_key"));
+
+    let (encapped_key, ciphertext) = config
+        .base_mode_seal(&keypair.public_key, APP_INFO, PLAINTEXT, AAD)
+        .unwrap()
+        .into_parts();
+    let plaintext = config
+        .base_mode_open_zeroizing(
+            &keypair.private_key,
+            &encapped_key,
+            APP_INFO,
+            &ciphertext,
+            AAD,
+        )
+        .unwrap();
+    assert_eq!(*plaintext, PLAINTEXT);
+}
+
+#[test]
+fn test_typed_keys() {
+    for kem in KEM_ALL {
+        let config = Config {
+            aead: encrypting_aeads()[0],
+            kdf: KDF_ALL[0],
+            kem: *kem,
+        };
+        let keypair = kem.gen_keypair();
+        let private_key = PrivateKey::new(*kem, &keypair.private_key).unwrap();
+        let public_key = PublicKey::new(*kem, &keypair.public_key).unwrap();
+        assert_eq!(private_key.public_key(), public_key);
+
+        let EncappedKeyAndCiphertext {
+            encapped_key,
+            ciphertext,
+        } = config
+            .base_mode_seal(&public_key, APP_INFO, PLAINTEXT, AAD)
+            .unwrap();
+        let encapped_key = EncappedKey::new(*kem, &encapped_key).unwrap();
+        assert_eq!(
+            config
+                .base_mode_open(&private_key, &encapped_key, APP_INFO, &ciphertext, AAD)
+                .unwrap(),
+            PLAINTEXT
+        );
+
+        assert_eq!(
+            PublicKey::new(*kem, &keypair.public_key[1..]),
+            Err(Error::InvalidPublicKey { kem: *kem })
+        );
+        assert_eq!(
+            PrivateKey::new(*kem, &[]).unwrap_err(),
+            Error::InvalidPrivateKey { kem: *kem }
+        );
+        assert_eq!(
+            EncappedKey::new(*kem, &[]),
+            Err(Error::InvalidEncappedKey { kem: *kem })
+        );
+    }
+}
+
+#[test]
+fn test_typed_key_kem_mismatch() {
+    let config = Config {
+        aead: AEAD_ALL[0],
+        kdf: KDF_ALL[0],
+        kem: KEM_ALL[0],
+    };
+    // a mismatch needs a second kem
+    let Some(&other_kem) = KEM_ALL.get(1) else {
+        return;
+    };
+    let keypair = other_kem.gen_keypair();
+    let public_key = PublicKey::new(other_kem, &keypair.public_key).unwrap();
+    let private_key = PrivateKey::new(other_kem, &keypair.private_key).unwrap();
+    let mismatch = Error::KemMismatch {
+        expected: config.kem,
+        found: other_kem,
+    };
+
+    assert_eq!(
+        config
+            .base_mode_seal(&public_key, APP_INFO, PLAINTEXT, AAD)
+            .unwrap_err(),
+        mismatch
+    );
+    assert_eq!(
+        config
+            .setup_receiver(&private_key, &[0; 32], APP_INFO)
+            .unwrap_err(),
+        mismatch
+    );
+    assert_eq!(
+        format!("{private_key:?}"),
+        format!("PrivateKey {{ kem: {other_kem:?}, .. }}")
+    );
+}
+
+#[test]
+fn test_psk_validation() {
+    let config = Config {
+        aead: AEAD_ALL[0],
+        kdf: KDF_ALL[0],
+        kem: KEM_ALL[0],
+    };
+    let public_key = config.kem.gen_keypair().public_key;
+
+    let error = Err(Error::InvalidPsk { kdf: config.kdf });
+    assert_eq!(
+        config.psk_mode_seal(
+            &public_key,
+            &PSK[..MIN_PSK_LEN - 1],
+            PSK_ID,
+            APP_INFO,
+            PLAINTEXT,
+            AAD
+        ),
+        error
+    );
+    assert_eq!(
+        config.psk_mode_seal(&public_key, PSK, b"", APP_INFO, PLAINTEXT, AAD),
+        error
+    );
+}
+
+#[cfg(feature = "shake")]
+#[test]
+fn test_key_schedule_input_too_long() {
+    let config = Config {
+        aead: encrypting_aeads()[0],
+        kdf: Kdf::Shake256,
+        kem: KEM_ALL[0],
+    };
+    let (private_key, public_key) = config.kem.gen_keypair().into_parts();
+    let info = vec![0; 65536];
+    let error = Error::KeyScheduleInputTooLong { kdf: config.kdf };
+
+    assert_eq!(config.setup_sender(&public_key, &info).unwrap_err(), error);
+    assert_eq!(
+        config
+            .sender_export(&public_key, &info, b"exporter context", 32)
+            .unwrap_err(),
+        error
+    );
+    assert_eq!(
+        config
+            .base_mode_seal(&public_key, &info, PLAINTEXT, AAD)
+            .unwrap_err(),
+        error
+    );
+    assert_eq!(
+        config
+            .psk_mode_seal(&public_key, PSK, &info, APP_INFO, PLAINTEXT, AAD)
+            .unwrap_err(),
+        error
+    );
+
+    // the longest input that can be length-prefixed is accepted
+    let info = &info[..65535];
+    let (encapped_key, mut sender) = config.setup_sender(&public_key, info).unwrap();
+    let ciphertext = sender.seal(PLAINTEXT, AAD).unwrap();
+    assert_eq!(
+        config
+            .base_mode_open(&private_key, &encapped_key, info, &ciphertext, AAD)
+            .unwrap(),
+        PLAINTEXT
+    );
+}
+
+#[test]
+fn test_export_only() {
+    let config = Config::try_from_ids(0xFFFF, KDF_ALL[0] as u16, KEM_ALL[0] as u16).unwrap();
+    assert_eq!(config.aead, Aead::ExportOnly);
+    assert_eq!("export-only".parse::<Aead>().unwrap(), Aead::ExportOnly);
+
+    let (private_key, public_key) = config.kem.gen_keypair().into_parts();
+    assert_eq!(
+        config.base_mode_seal(&public_key, APP_INFO, PLAINTEXT, AAD),
+        Err(Error::ExportOnly { aead: config.aead })
+    );
+
+    let (encapped_key, mut sender) = config.setup_sender(&public_key, APP_INFO).unwrap();
+    assert_eq!(
+        sender.seal(PLAINTEXT, AAD),
+        Err(Error::ExportOnly { aead: config.aead })
+    );
+
+    let mut receiver = config
+        .setup_receiver(&private_key, &encapped_key, APP_INFO)
+        .unwrap();
+    assert_eq!(
+        receiver.open(PLAINTEXT, AAD),
+        Err(Error::ExportOnly { aead: config.aead })
+    );
+    assert_eq!(
+        sender.export(b"exporter context", 32).unwrap(),
+        receiver.export(b"exporter context", 32).unwrap()
+    );
+}
+
+#[test]
+fn test_export_only_all_kdfs() {
+    for kdf in KDF_ALL {
+        test_export_round_trip(Config {
+            aead: Aead::ExportOnly,
+            kdf: *kdf,
+            kem: KEM_ALL[0],
         });
     }
 }
+
+#[test]
+fn test_display_round_trip() {
+    for aead in AEAD_ALL {
+        assert_eq!(aead.to_string().parse::<Aead>(), Ok(*aead));
+    }
+    for kdf in KDF_ALL {
+        assert_eq!(kdf.to_string().parse::<Kdf>(), Ok(*kdf));
+    }
+    for kem in KEM_ALL {
+        assert_eq!(kem.to_string().parse::<Kem>(), Ok(*kem));
+        for aead in AEAD_ALL {
+            let config = Config {
+                aead: *aead,
+                kdf: KDF_ALL[0],
+                kem: *kem,
+            };
+            assert_eq!(config.to_string().parse::<Config>(), Ok(config));
+        }
+    }
+
+    #[cfg(all(feature = "aes", feature = "hkdfsha2", feature = "x25519"))]
+    {
+        let config = Config {
+            aead: Aead::AesGcm128,
+            kdf: Kdf::Sha256,
+            kem: Kem::X25519HkdfSha256,
+        };
+        assert_eq!(
+            config.to_string(),
+            "DHKEM(X25519, HKDF-SHA256)/HKDF-SHA256/AES-128-GCM"
+        );
+        assert_eq!("x25519-sha256:sha256:aes128gcm".parse(), Ok(config));
+        assert_eq!(" x25519-sha256 / sha256 / aes128gcm ".parse(), Ok(config));
+    }
+    #[cfg(all(feature = "mlkem", feature = "x25519"))]
+    assert_eq!(Kem::XWing.to_string(), "MLKEM768-X25519");
+    assert_eq!(Aead::ExportOnly.to_string(), "Export-only");
+
+    assert_eq!(
+        "x25519-sha256/sha256".parse::<Config>(),
+        Err(Error::InvalidConfigString)
+    );
+    assert_eq!(
+        format!(
+            "{}/{}/{}/{}",
+            KEM_ALL[0], KDF_ALL[0], AEAD_ALL[0], AEAD_ALL[0]
+        )
+        .parse::<Config>(),
+        Err(Error::InvalidConfigString)
+    );
+    let error = format!("{}/{}/rot13", KEM_ALL[0], KDF_ALL[0])
+        .parse::<Config>()
+        .unwrap_err();
+    let Error::UnsupportedAlgorithm(lookup) = &error else {
+        panic!("unexpected error {error:?}");
+    };
+    assert_eq!(lookup.kind(), AlgorithmKind::Aead);
+    assert_eq!(lookup.value(), &LookupValue::Name("rot13".into()));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_export_only_serde() {
+    let serialized = serde_json::to_string(&Aead::ExportOnly).unwrap();
+    assert_eq!(
+        serde_json::from_str::<Aead>(&serialized).unwrap(),
+        Aead::ExportOnly
+    );
+}
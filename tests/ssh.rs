@@ -0,0 +1,105 @@
+#![cfg(all(feature = "ssh", feature = "nistp", feature = "x25519"))]
+
+use hpke_dispatch::*;
+
+/// keys generated with `ssh-keygen -N ""`
+const OPENSSH_KEYS: &[(Kem, &str, &str)] = &[
+    (
+        Kem::X25519HkdfSha256,
+        include_str!("keys/ssh_ed25519"),
+        include_str!("keys/ssh_ed25519.pub"),
+    ),
+    (
+        Kem::DhP256HkdfSha256,
+        include_str!("keys/ssh_p256"),
+        include_str!("keys/ssh_p256.pub"),
+    ),
+    (
+        Kem::DhP384HkdfSha384,
+        include_str!("keys/ssh_p384"),
+        include_str!("keys/ssh_p384.pub"),
+    ),
+    (
+        Kem::DhP521HkdfSha512,
+        include_str!("keys/ssh_p521"),
+        include_str!("keys/ssh_p521.pub"),
+    ),
+];
+
+#[test]
+fn test_openssh_keys() {
+    for (kem, private_key, public_key) in OPENSSH_KEYS {
+        let (decoded_kem, keypair) = Keypair::from_openssh(private_key)
+            .unwrap_or_else(|e| panic!("problem with {kem:?} from_openssh ({e:?})"));
+        assert_eq!(decoded_kem, *kem);
+        keypair.validate(*kem).unwrap();
+
+        let public_key = PublicKey::from_openssh(public_key).unwrap();
+        assert_eq!(public_key.kem(), *kem);
+        assert_eq!(public_key.as_bytes(), keypair.public_key);
+
+        let config = Config {
+            aead: Aead::AesGcm128,
+            kdf: Kdf::Sha256,
+            kem: *kem,
+        };
+        let (encapped_key, ciphertext) = config
+            .base_mode_seal(&public_key, b"info", b"plaintext", b"aad")
+            .unwrap()
+            .into_parts();
+        let plaintext = config
+            .base_mode_open(
+                &keypair.private_key,
+                &encapped_key,
+                b"info",
+                &ciphertext,
+                b"aad",
+            )
+            .unwrap();
+        assert_eq!(plaintext, b"plaintext");
+    }
+}
+
+#[test]
+fn test_openssh_ed25519_conversion() {
+    // the Montgomery u-coordinate of the Edwards point, computed as (1 + y) / (1 - y)
+    let public_key = PublicKey::from_openssh(OPENSSH_KEYS[0].2).unwrap();
+    assert_eq!(
+        hex::encode(public_key.as_bytes()),
+        "725e2661508e2858251eace904aeda398febb6c6ee5b9bc7e71610bf63b7cc22"
+    );
+}
+
+#[test]
+fn test_invalid_openssh_keys() {
+    let (_, private_key, public_key) = OPENSSH_KEYS[0];
+
+    assert_eq!(
+        Keypair::from_openssh(include_str!("keys/ssh_ed25519_encrypted")),
+        Err(Error::InvalidKeyEncoding)
+    );
+    assert_eq!(
+        Keypair::from_openssh(public_key),
+        Err(Error::InvalidKeyEncoding)
+    );
+    assert_eq!(
+        PublicKey::from_openssh(private_key),
+        Err(Error::InvalidKeyEncoding)
+    );
+
+    // the key type name does not match the encoded key
+    let renamed = public_key.replace("ssh-ed25519", "ecdsa-sha2-nistp256");
+    assert_eq!(
+        PublicKey::from_openssh(&renamed),
+        Err(Error::InvalidKeyEncoding)
+    );
+
+    assert_eq!(
+        PublicKey::from_openssh("ssh-rsa AAAAB3NzaC1yc2EAAAADAQABAAAAgQDFMQr72/Dsz3Xya04BIp86"),
+        Err(Error::InvalidKeyEncoding)
+    );
+    assert_eq!(
+        PublicKey::from_openssh("ssh-ed25519"),
+        Err(Error::InvalidKeyEncoding)
+    );
+}
@@ -0,0 +1,184 @@
+use std::io::{ErrorKind, Read, Write};
+
+use hpke_dispatch::*;
+
+mod common;
+use common::encrypting_configs;
+
+const APP_INFO: &[u8] = b"application info";
+const CHUNK_SIZE: usize = 16;
+
+fn seal_stream(config: &Config, public_key: &[u8], plaintext: &[u8]) -> Vec<u8> {
+    let mut writer =
+        SealingWriter::with_chunk_size(config, public_key, APP_INFO, CHUNK_SIZE, Vec::new())
+            .unwrap_or_else(|e| panic!("problem with {config:?} SealingWriter ({e:?})"));
+    // write in pieces that do not line up with chunk boundaries
+    for piece in plaintext.chunks(7) {
+        writer.write_all(piece).unwrap();
+    }
+    writer.finish().unwrap()
+}
+
+fn open_stream(config: &Config, private_key: &[u8], stream: &[u8]) -> std::io::Result<Vec<u8>> {
+    let mut reader = OpeningReader::new(config, private_key, APP_INFO, stream)?;
+    let mut plaintext = Vec::new();
+    reader.read_to_end(&mut plaintext)?;
+    Ok(plaintext)
+}
+
+/// splits a sealed stream into its header and chunks
+fn split_stream(stream: &[u8]) -> (Vec<u8>, Vec<Vec<u8>>) {
+    let header_len = 2 + u16::from_be_bytes([stream[0], stream[1]]) as usize;
+    let (header, mut rest) = stream.split_at(header_len);
+    let mut chunks = Vec::new();
+    while !rest.is_empty() {
+        let len = u32::from_be_bytes(rest[1..5].try_into().unwrap()) as usize;
+        let (chunk, tail) = rest.split_at(5 + len);
+        chunks.push(chunk.to_vec());
+        rest = tail;
+    }
+    (header.to_vec(), chunks)
+}
+
+#[test]
+fn test_stream_round_trips() {
+    for config in encrypting_configs() {
+        let (private_key, public_key) = config.kem.gen_keypair().into_parts();
+        for len in [
+            0,
+            1,
+            CHUNK_SIZE - 1,
+            CHUNK_SIZE,
+            CHUNK_SIZE + 1,
+            5 * CHUNK_SIZE,
+        ] {
+            let plaintext = (0..len).map(|i| i as u8).collect::<Vec<_>>();
+            let stream = seal_stream(&config, &public_key, &plaintext);
+            let opened = open_stream(&config, &private_key, &stream)
+                .unwrap_or_else(|e| panic!("problem with {config:?} OpeningReader ({e:?})"));
+            assert_eq!(opened, plaintext, "stream did not match for {config:?}");
+        }
+    }
+}
+
+#[test]
+fn test_stream_default_chunk_size() {
+    let config = encrypting_configs().next().unwrap();
+    let (private_key, public_key) = config.kem.gen_keypair().into_parts();
+    let plaintext = vec![0xab; 2 * DEFAULT_CHUNK_SIZE + 3];
+
+    let mut writer = SealingWriter::new(&config, &public_key, APP_INFO, Vec::new()).unwrap();
+    writer.write_all(&plaintext).unwrap();
+    let stream = writer.finish().unwrap();
+
+    assert_eq!(split_stream(&stream).1.len(), 3);
+    assert_eq!(
+        open_stream(&config, &private_key, &stream).unwrap(),
+        plaintext
+    );
+}
+
+#[test]
+fn test_stream_tampering() {
+    let config = encrypting_configs().next().unwrap();
+    let (private_key, public_key) = config.kem.gen_keypair().into_parts();
+    let stream = seal_stream(&config, &public_key, &[7; 3 * CHUNK_SIZE + 1]);
+    let (header, chunks) = split_stream(&stream);
+    assert_eq!(chunks.len(), 4);
+
+    let reassemble = |chunks: &[Vec<u8>]| [header.clone(), chunks.concat()].concat();
+    let open_error = |stream: &[u8]| open_stream(&config, &private_key, stream).unwrap_err();
+
+    // truncated at a chunk boundary, or in the middle of a chunk
+    assert_eq!(
+        open_error(&reassemble(&chunks[..3])).kind(),
+        ErrorKind::UnexpectedEof
+    );
+    assert_eq!(
+        open_error(&stream[..stream.len() - 1]).kind(),
+        ErrorKind::UnexpectedEof
+    );
+
+    // reordered chunks
+    let reordered = [
+        chunks[1].clone(),
+        chunks[0].clone(),
+        chunks[2].clone(),
+        chunks[3].clone(),
+    ];
+    assert_eq!(
+        open_error(&reassemble(&reordered)).kind(),
+        ErrorKind::InvalidData
+    );
+
+    // a non-final chunk marked as final
+    let mut marked_final = chunks.clone();
+    marked_final[2][0] = 1;
+    assert_eq!(
+        open_error(&reassemble(&marked_final[..3])).kind(),
+        ErrorKind::InvalidData
+    );
+
+    // an unknown chunk flag
+    let mut bad_flag = chunks.clone();
+    bad_flag[0][0] = 2;
+    assert_eq!(
+        open_error(&reassemble(&bad_flag)).kind(),
+        ErrorKind::InvalidData
+    );
+
+    // data following the final chunk
+    let error = open_error(&[&stream[..], &[0]].concat());
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    assert_eq!(
+        error.into_inner().unwrap().downcast::<Error>().unwrap(),
+        Box::new(Error::MalformedStream)
+    );
+}
+
+#[test]
+fn test_stream_invalid_chunk_size() {
+    let config = encrypting_configs().next().unwrap();
+    let public_key = config.kem.gen_keypair().public_key;
+    for chunk_size in [0, MAX_CHUNK_SIZE + 1] {
+        let error =
+            SealingWriter::with_chunk_size(&config, &public_key, APP_INFO, chunk_size, Vec::new())
+                .unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidInput);
+    }
+}
+
+/// a reader that is interrupted before each read
+struct Interrupting<R> {
+    inner: R,
+    interrupted: bool,
+}
+
+impl<R: Read> Read for Interrupting<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.interrupted = !self.interrupted;
+        if self.interrupted {
+            return Err(ErrorKind::Interrupted.into());
+        }
+        self.inner.read(buf)
+    }
+}
+
+#[test]
+fn test_stream_interrupted() {
+    let config = encrypting_configs().next().unwrap();
+    let (private_key, public_key) = config.kem.gen_keypair().into_parts();
+    let plaintext = [7; 2 * CHUNK_SIZE + 1];
+    let stream = seal_stream(&config, &public_key, &plaintext);
+
+    let inner = Interrupting {
+        inner: &stream[..],
+        interrupted: false,
+    };
+    let mut reader = OpeningReader::new(&config, &private_key, APP_INFO, inner).unwrap();
+    let mut opened = [0; 2 * CHUNK_SIZE + 1];
+    reader.read_exact(&mut opened).unwrap();
+    assert_eq!(opened, plaintext);
+    // the check for data after the final chunk is interrupted, and retried
+    assert_eq!(reader.read(&mut [0]).unwrap(), 0);
+}
@@ -1,4 +1,4 @@
-use hpke_dispatch::Config;
+use hpke_dispatch::{Aead, Config, Error, KDF_ALL};
 use serde_crate::Deserialize;
 
 #[derive(Deserialize, Debug)]
@@ -14,6 +14,17 @@ struct EncryptionRecord {
     plaintext: Vec<u8>,
 }
 
+#[derive(Deserialize, Debug)]
+#[serde(crate = "serde_crate")]
+struct ExportRecord {
+    #[serde(with = "hex")]
+    exporter_context: Vec<u8>,
+    #[serde(rename = "L")]
+    len: usize,
+    #[serde(with = "hex")]
+    exported_value: Vec<u8>,
+}
+
 /// This structure corresponds to the format of the JSON test vectors included with the HPKE
 /// RFC. Only a subset of fields are used; all intermediate calculations are ignored.
 #[derive(Deserialize, Debug)]
@@ -29,15 +40,29 @@ struct TestVector {
     encapped_key: Vec<u8>,
     #[serde(with = "hex", rename = "skRm")]
     serialized_private_key: Vec<u8>,
+    #[serde(with = "hex", rename = "pkRm")]
+    serialized_public_key: Vec<u8>,
+    #[serde(with = "hex", rename = "ikmR")]
+    recipient_ikm: Vec<u8>,
+    #[serde(with = "hex")]
+    key: Vec<u8>,
     #[serde(with = "hex")]
     base_nonce: Vec<u8>,
+    #[serde(with = "hex")]
+    exporter_secret: Vec<u8>,
+    #[serde(with = "hex", default)]
+    psk: Vec<u8>,
+    #[serde(with = "hex", default)]
+    psk_id: Vec<u8>,
+    #[serde(with = "hex", default, rename = "pkSm")]
+    sender_public_key: Vec<u8>,
     encryptions: Vec<EncryptionRecord>,
+    exports: Vec<ExportRecord>,
 }
 
-fn decrypt_test_vector(test_vectors: Vec<TestVector>) {
-    let test_vectors = test_vectors
+fn supported_test_vectors(test_vectors: Vec<TestVector>) -> Vec<(Config, TestVector)> {
+    test_vectors
         .into_iter()
-        .filter(|v| v.mode == 0)
         .filter_map(|test_vector| {
             let config =
                 Config::try_from_ids(test_vector.aead_id, test_vector.kdf_id, test_vector.kem_id)
@@ -45,28 +70,249 @@ fn decrypt_test_vector(test_vectors: Vec<TestVector>) {
 
             Some((config, test_vector))
         })
-        .collect::<Vec<_>>();
+        .collect()
+}
 
-    for (config, test_vector) in test_vectors {
+fn decrypt_test_vector(test_vectors: Vec<TestVector>) {
+    for (config, test_vector) in supported_test_vectors(test_vectors) {
         for encryption in &test_vector.encryptions {
             if encryption.nonce != test_vector.base_nonce {
                 continue;
             }
 
-            let plaintext = config
-                .base_mode_open(
+            let plaintext = match test_vector.mode {
+                0 => config.base_mode_open(
                     &test_vector.serialized_private_key,
                     &test_vector.encapped_key,
                     &test_vector.info,
                     &encryption.ciphertext,
                     &encryption.aad,
-                )
+                ),
+                1 => config.psk_mode_open(
+                    &test_vector.serialized_private_key,
+                    &test_vector.encapped_key,
+                    &test_vector.psk,
+                    &test_vector.psk_id,
+                    &test_vector.info,
+                    &encryption.ciphertext,
+                    &encryption.aad,
+                ),
+                2 => config.auth_mode_open(
+                    &test_vector.serialized_private_key,
+                    &test_vector.sender_public_key,
+                    &test_vector.encapped_key,
+                    &test_vector.info,
+                    &encryption.ciphertext,
+                    &encryption.aad,
+                ),
+                3 => config.auth_psk_mode_open(
+                    &test_vector.serialized_private_key,
+                    &test_vector.sender_public_key,
+                    &test_vector.encapped_key,
+                    &test_vector.psk,
+                    &test_vector.psk_id,
+                    &test_vector.info,
+                    &encryption.ciphertext,
+                    &encryption.aad,
+                ),
+                mode => unreachable!("unexpected mode {mode}"),
+            }
+            .unwrap_or_else(|e| {
+                panic!("{e:?}\n\n{config:?}\n\n{encryption:#?}\n\n{test_vector:#?}")
+            });
+
+            assert_eq!(plaintext, encryption.plaintext);
+        }
+    }
+}
+
+fn decrypt_test_vector_stateful(test_vectors: Vec<TestVector>) {
+    for (config, test_vector) in supported_test_vectors(test_vectors) {
+        if test_vector.mode != 0 {
+            continue;
+        }
+
+        let mut receiver = config
+            .setup_receiver(
+                &test_vector.serialized_private_key,
+                &test_vector.encapped_key,
+                &test_vector.info,
+            )
+            .unwrap_or_else(|e| panic!("{e:?}\n\n{config:?}\n\n{test_vector:#?}"));
+
+        if config.aead == Aead::ExportOnly {
+            assert_eq!(
+                receiver.open_at(0, &[], &[]),
+                Err(Error::ExportOnly { aead: config.aead })
+            );
+        }
+
+        // the encryptions are listed in sequence number order
+        for (seq, encryption) in test_vector.encryptions.iter().enumerate().rev() {
+            let plaintext = receiver
+                .open_at(seq as u64, &encryption.ciphertext, &encryption.aad)
                 .unwrap_or_else(|e| {
                     panic!("{e:?}\n\n{config:?}\n\n{encryption:#?}\n\n{test_vector:#?}")
                 });
 
             assert_eq!(plaintext, encryption.plaintext);
         }
+
+        for encryption in &test_vector.encryptions {
+            let plaintext = receiver
+                .open(&encryption.ciphertext, &encryption.aad)
+                .unwrap_or_else(|e| {
+                    panic!("{e:?}\n\n{config:?}\n\n{encryption:#?}\n\n{test_vector:#?}")
+                });
+
+            assert_eq!(plaintext, encryption.plaintext);
+        }
+
+        for export in &test_vector.exports {
+            let secret = receiver
+                .export(&export.exporter_context, export.len)
+                .unwrap_or_else(|e| {
+                    panic!("{e:?}\n\n{config:?}\n\n{export:#?}\n\n{test_vector:#?}")
+                });
+
+            assert_eq!(*secret, export.exported_value);
+        }
+    }
+}
+
+/// The saved state of a context with the secrets of `test_vector` at sequence number `seq`, laid
+/// out as described by `STATE_VERSION` in src/context.rs.
+fn saved_state(config: &Config, test_vector: &TestVector, receiver: bool, seq: u64) -> Vec<u8> {
+    let mut state = vec![1, u8::from(receiver)];
+    for id in [
+        u16::from(config.aead),
+        u16::from(config.kdf),
+        u16::from(config.kem),
+    ] {
+        state.extend_from_slice(&id.to_be_bytes());
+    }
+    state.push(1);
+    state.extend_from_slice(&seq.to_be_bytes());
+    for secret in [
+        &test_vector.key,
+        &test_vector.base_nonce,
+        &test_vector.exporter_secret,
+    ] {
+        state.extend_from_slice(&(secret.len() as u16).to_be_bytes());
+        state.extend_from_slice(secret);
+    }
+    state
+}
+
+/// Check that the key schedule derives the secrets of each test vector, and that contexts resumed
+/// from those secrets seal, open and export as the test vector does. Returns the ids of the
+/// [`Kdf`](hpke_dispatch::Kdf)s that were checked.
+fn resume_test_vector(test_vectors: Vec<TestVector>) -> Vec<u16> {
+    let mut kdf_ids = Vec::new();
+    for (config, test_vector) in supported_test_vectors(test_vectors) {
+        kdf_ids.push(test_vector.kdf_id);
+        let context = || format!("{config:?}\n\n{test_vector:#?}");
+
+        if test_vector.mode == 0 {
+            let receiver = config
+                .setup_receiver(
+                    &test_vector.serialized_private_key,
+                    &test_vector.encapped_key,
+                    &test_vector.info,
+                )
+                .unwrap_or_else(|e| panic!("{e:?}\n\n{}", context()));
+            assert_eq!(
+                *receiver.save_state(),
+                saved_state(&config, &test_vector, true, 0),
+                "{}",
+                context()
+            );
+        }
+
+        // the encryptions are listed in sequence number order
+        for (seq, encryption) in test_vector.encryptions.iter().enumerate() {
+            let seq = seq as u64;
+            let mut sender = config
+                .resume_sender(&saved_state(&config, &test_vector, false, seq))
+                .unwrap_or_else(|e| panic!("{e:?}\n\n{}", context()));
+            let ciphertext = sender
+                .seal(&encryption.plaintext, &encryption.aad)
+                .unwrap_or_else(|e| panic!("{e:?}\n\n{encryption:#?}\n\n{}", context()));
+            assert_eq!(ciphertext, encryption.ciphertext, "{}", context());
+            assert_eq!(
+                *sender.save_state(),
+                saved_state(&config, &test_vector, false, seq + 1)
+            );
+        }
+
+        let mut receiver = config
+            .resume_receiver(&saved_state(&config, &test_vector, true, 0))
+            .unwrap_or_else(|e| panic!("{e:?}\n\n{}", context()));
+        for encryption in &test_vector.encryptions {
+            let plaintext = receiver
+                .open(&encryption.ciphertext, &encryption.aad)
+                .unwrap_or_else(|e| panic!("{e:?}\n\n{encryption:#?}\n\n{}", context()));
+            assert_eq!(plaintext, encryption.plaintext);
+        }
+
+        let sender = config
+            .resume_sender(&saved_state(&config, &test_vector, false, 0))
+            .unwrap_or_else(|e| panic!("{e:?}\n\n{}", context()));
+        for export in &test_vector.exports {
+            for secret in [
+                sender.export(&export.exporter_context, export.len),
+                receiver.export(&export.exporter_context, export.len),
+            ] {
+                let secret =
+                    secret.unwrap_or_else(|e| panic!("{e:?}\n\n{export:#?}\n\n{}", context()));
+                assert_eq!(*secret, export.exported_value);
+            }
+        }
+    }
+    kdf_ids
+}
+
+fn export_test_vector(test_vectors: Vec<TestVector>) {
+    for (config, test_vector) in supported_test_vectors(test_vectors) {
+        if test_vector.mode != 0 {
+            continue;
+        }
+
+        for export in &test_vector.exports {
+            let secret = config
+                .receiver_export(
+                    &test_vector.serialized_private_key,
+                    &test_vector.encapped_key,
+                    &test_vector.info,
+                    &export.exporter_context,
+                    export.len,
+                )
+                .unwrap_or_else(|e| {
+                    panic!("{e:?}\n\n{config:?}\n\n{export:#?}\n\n{test_vector:#?}")
+                });
+
+            assert_eq!(*secret, export.exported_value);
+        }
+    }
+}
+
+fn derive_keypair_test_vector(test_vectors: Vec<TestVector>) {
+    for (config, test_vector) in supported_test_vectors(test_vectors) {
+        let keypair = config
+            .kem
+            .derive_keypair(&test_vector.recipient_ikm)
+            .unwrap_or_else(|e| panic!("{e:?}\n\n{config:?}\n\n{test_vector:#?}"));
+
+        assert_eq!(*keypair.private_key, test_vector.serialized_private_key);
+        assert_eq!(keypair.public_key, test_vector.serialized_public_key);
+        assert_eq!(
+            config
+                .kem
+                .public_key_from_private(&test_vector.serialized_private_key)
+                .unwrap(),
+            test_vector.serialized_public_key
+        );
+        assert_eq!(keypair.validate(config.kem), Ok(()));
     }
 }
 
@@ -78,6 +324,27 @@ fn test_vector_rfc() {
     decrypt_test_vector(test_vectors);
 }
 
+#[test]
+fn test_vector_rfc_stateful() {
+    let test_vectors: Vec<TestVector> =
+        serde_json::from_str(include_str!("./test-vectors.json")).unwrap();
+    decrypt_test_vector_stateful(test_vectors);
+}
+
+#[test]
+fn test_vector_rfc_export() {
+    let test_vectors: Vec<TestVector> =
+        serde_json::from_str(include_str!("./test-vectors.json")).unwrap();
+    export_test_vector(test_vectors);
+}
+
+#[test]
+fn test_vector_rfc_derive_keypair() {
+    let test_vectors: Vec<TestVector> =
+        serde_json::from_str(include_str!("./test-vectors.json")).unwrap();
+    derive_keypair_test_vector(test_vectors);
+}
+
 #[test]
 fn test_vector_pq_hybrid() {
     // https://github.com/hpkewg/hpke-pq/blob/11b5b9541e9976fc9ce25902011d20dacc089066/test-vectors.json
@@ -85,3 +352,62 @@ fn test_vector_pq_hybrid() {
         serde_json::from_str(include_str!("./test-vectors-pq.json")).unwrap();
     decrypt_test_vector(test_vectors);
 }
+
+#[test]
+fn test_vector_pq_hybrid_stateful() {
+    let test_vectors: Vec<TestVector> =
+        serde_json::from_str(include_str!("./test-vectors-pq.json")).unwrap();
+    decrypt_test_vector_stateful(test_vectors);
+}
+
+#[test]
+fn test_vector_pq_hybrid_export() {
+    let test_vectors: Vec<TestVector> =
+        serde_json::from_str(include_str!("./test-vectors-pq.json")).unwrap();
+    export_test_vector(test_vectors);
+}
+
+#[test]
+fn test_vector_pq_hybrid_derive_keypair() {
+    let test_vectors: Vec<TestVector> =
+        serde_json::from_str(include_str!("./test-vectors-pq.json")).unwrap();
+    derive_keypair_test_vector(test_vectors);
+}
+
+#[test]
+fn test_vector_export_only_one_stage() {
+    // the published vectors have no export-only entries for the one-stage kdfs, so these are
+    // derived from test-vectors-pq.json by export-only-vectors.py
+    let test_vectors: Vec<TestVector> =
+        serde_json::from_str(include_str!("./test-vectors-export-only.json")).unwrap();
+    assert!(test_vectors
+        .iter()
+        .all(|v| v.aead_id == u16::from(Aead::ExportOnly)));
+    decrypt_test_vector_stateful(test_vectors);
+}
+
+#[test]
+fn test_vector_export_only_one_stage_export() {
+    let test_vectors: Vec<TestVector> =
+        serde_json::from_str(include_str!("./test-vectors-export-only.json")).unwrap();
+    export_test_vector(test_vectors);
+}
+
+#[test]
+fn test_vector_resume() {
+    let mut kdf_ids = Vec::new();
+    for test_vectors in [
+        include_str!("./test-vectors.json"),
+        include_str!("./test-vectors-pq.json"),
+        include_str!("./test-vectors-export-only.json"),
+    ] {
+        kdf_ids.extend(resume_test_vector(
+            serde_json::from_str(test_vectors).unwrap(),
+        ));
+    }
+
+    // every kdf, and so both the two-stage and one-stage key schedules, is checked
+    for kdf in KDF_ALL {
+        assert!(kdf_ids.contains(&u16::from(*kdf)), "{kdf:?}");
+    }
+}